@@ -0,0 +1,123 @@
+language-name = English
+
+start-begin = Press { $key } to Begin
+start-daily = Press { $key } for the daily challenge
+start-seed = Press { $key } to enter a seed code
+start-explore = Press { $key } to Explore
+start-tour = Press { $key } for a guided tour : { $name } (press { $change } for another one)
+start-bindings = Press { $key } to change key bindings
+start-settings = Press { $key } for settings
+culture-label = Sky culture : { $name } (press { $key } to change)
+language-label = Language : { $language } (press { $key } to change)
+
+hint-recenter = press { $key } to re-center
+hint-get = press { $key } to get an hint
+hint-continue = press { $key } to continue
+hint-guess = guess the constellation
+
+tutorial-pan = Drag the sky with the left mouse button (or one finger) to look around
+tutorial-rotate = Drag around the middle of the screen with the right mouse button (or twist two fingers) to roll the sky
+tutorial-zoom = Scroll the mouse wheel (or pinch) to zoom in and out
+tutorial-recenter = Here is a constellation to find. Look away from it, then press { $recenter } (or tap the line at the top) to turn back to it
+tutorial-hint = Press { $hint } for a hint : its figure is drawn, but a right answer is then worth 20 points instead of 100
+tutorial-answer = Now pick its name among the four answers
+tutorial-help = { $key } : skip the tutorial
+
+measure-first = from { $from }, press { $key } again on the second point
+measure-angle = { $from } to { $to } : { $angle }, { $hands }
+measure-less-than-finger = less than a finger width at arm's length
+measure-finger = about a finger width at arm's length
+measure-fingers = about { $count } finger widths at arm's length
+measure-fist = about a fist at arm's length
+measure-fists = about { $count } fists at arm's length
+instrument-label = { $name } : { $magnification }x, { $field } field
+
+end-title = Game Over
+end-score = final score : { $score }
+end-code = seed code : { $code }
+end-daily = daily challenge of { $date } ({ $code })
+end-continue = press { $key } to continue
+tour-help = { $next } : next step, { $back } : leave the tour
+end-card = press { $key } to save your result card
+result-found = { $found } of { $asked } constellations found
+
+bindings-title = Key bindings
+bindings-help = Up and Down to select, Enter to rebind, { $key } to go back
+bindings-waiting = press a key, { $key } to cancel
+
+seed-title = Seed code
+seed-help = Type the code and press Enter, { $key } to go back
+seed-invalid = unknown code
+
+settings-title = Settings
+settings-help = Up and Down to select, Left and Right to change, { $key } to go back
+setting-drag-sensitivity = Drag sensitivity
+setting-star-size = Star size
+setting-line-brightness = Line brightness
+setting-master-volume = Master volume
+setting-music-volume = Music volume
+setting-sfx-volume = Sound effects volume
+setting-mute = Mute
+setting-language = Language
+setting-difficulty = Difficulty
+setting-zoom-to-cursor = Zoom toward the cursor
+setting-bortle = Light pollution
+setting-limiting-magnitude = Faintest star magnitude
+setting-atmosphere = Atmosphere
+setting-labels = Sky labels in explore mode
+setting-tutorial = Tutorial before the next game
+bortle-1 = Bortle 1, excellent dark site
+bortle-2 = Bortle 2, typical dark site
+bortle-3 = Bortle 3, rural sky
+bortle-4 = Bortle 4, rural to suburban
+bortle-5 = Bortle 5, suburban sky
+bortle-6 = Bortle 6, bright suburban sky
+bortle-7 = Bortle 7, suburban to urban
+bortle-8 = Bortle 8, city sky
+bortle-9 = Bortle 9, inner city
+setting-on = on
+setting-off = off
+difficulty-easy = Easy
+difficulty-normal = Normal
+difficulty-hard = Hard
+
+action-next = Begin / Next
+action-back = Back
+action-explore = Explore
+action-hint = Hint
+action-recenter = Re-center
+action-changeculture = Change sky culture
+action-changelanguage = Change language
+action-keybindings = Key bindings
+action-gyroscope = Gyroscope
+action-settings = Settings
+action-mute = Mute sound
+action-dailychallenge = Daily challenge
+action-enterseed = Enter a seed code
+action-labels = Sky labels
+action-chart = Export a star chart
+action-screenshot = Screenshot
+action-bookmark = Bookmark the view
+action-gotobookmark = Go to a bookmark
+action-tour = Guided tour
+action-changetour = Change the guided tour
+action-measure = Measure an angle
+action-instrument = Instrument field
+action-matchinstrument = Zoom to the instrument field
+
+card-best-months = Best viewing
+card-notable-stars = Notable stars
+card-deep-sky = Deep-sky objects
+
+month-1 = January
+month-2 = February
+month-3 = March
+month-4 = April
+month-5 = May
+month-6 = June
+month-7 = July
+month-8 = August
+month-9 = September
+month-10 = October
+month-11 = November
+month-12 = December
@@ -0,0 +1,266 @@
+language-name = Français
+
+start-begin = Appuyez sur { $key } pour commencer
+start-daily = Appuyez sur { $key } pour le défi du jour
+start-seed = Appuyez sur { $key } pour entrer un code de partie
+start-explore = Appuyez sur { $key } pour explorer
+start-tour = Appuyez sur { $key } pour une visite guidée : { $name } (appuyez sur { $change } pour une autre)
+start-bindings = Appuyez sur { $key } pour changer les touches
+start-settings = Appuyez sur { $key } pour les réglages
+culture-label = Culture céleste : { $name } (appuyez sur { $key } pour changer)
+language-label = Langue : { $language } (appuyez sur { $key } pour changer)
+
+hint-recenter = appuyez sur { $key } pour recentrer
+hint-get = appuyez sur { $key } pour obtenir un indice
+hint-continue = appuyez sur { $key } pour continuer
+hint-guess = devinez la constellation
+
+tutorial-pan = Faites glisser le ciel avec le bouton gauche de la souris (ou un doigt) pour regarder autour
+tutorial-rotate = Tournez autour du milieu de l'écran avec le bouton droit de la souris (ou deux doigts) pour faire pivoter le ciel
+tutorial-zoom = Utilisez la molette de la souris (ou pincez) pour zoomer et dézoomer
+tutorial-recenter = Voici une constellation à trouver. Regardez ailleurs, puis appuyez sur { $recenter } (ou touchez la ligne du haut) pour revenir dessus
+tutorial-hint = Appuyez sur { $hint } pour un indice : sa figure est tracée, mais une bonne réponse ne vaut alors plus que 20 points au lieu de 100
+tutorial-answer = Choisissez maintenant son nom parmi les quatre réponses
+tutorial-help = { $key } : passer le tutoriel
+
+measure-first = depuis { $from }, appuyez encore sur { $key } sur le second point
+measure-angle = de { $from } à { $to } : { $angle }, { $hands }
+measure-less-than-finger = moins d'un doigt à bout de bras
+measure-finger = environ un doigt à bout de bras
+measure-fingers = environ { $count } doigts à bout de bras
+measure-fist = environ un poing à bout de bras
+measure-fists = environ { $count } poings à bout de bras
+instrument-label = { $name } : { $magnification }x, champ de { $field }
+
+end-title = Partie terminée
+end-score = score final : { $score }
+end-code = code de la partie : { $code }
+end-daily = défi du { $date } ({ $code })
+end-continue = appuyez sur { $key } pour continuer
+tour-help = { $next } : étape suivante, { $back } : quitter la visite
+end-card = appuyez sur { $key } pour enregistrer votre carte de résultat
+result-found = { $found } constellations trouvées sur { $asked }
+
+bindings-title = Touches
+bindings-help = Haut et Bas pour choisir, Entrée pour modifier, { $key } pour revenir
+bindings-waiting = appuyez sur une touche, { $key } pour annuler
+
+seed-title = Code de partie
+seed-help = Tapez le code puis Entrée, { $key } pour revenir
+seed-invalid = code inconnu
+
+settings-title = Réglages
+settings-help = Haut et Bas pour choisir, Gauche et Droite pour modifier, { $key } pour revenir
+setting-drag-sensitivity = Sensibilité du glisser
+setting-star-size = Taille des étoiles
+setting-line-brightness = Luminosité des tracés
+setting-master-volume = Volume général
+setting-music-volume = Volume de la musique
+setting-sfx-volume = Volume des effets sonores
+setting-mute = Son coupé
+setting-language = Langue
+setting-difficulty = Difficulté
+setting-zoom-to-cursor = Zoomer vers le curseur
+setting-bortle = Pollution lumineuse
+setting-limiting-magnitude = Magnitude de l'étoile la plus faible
+setting-atmosphere = Atmosphère
+setting-labels = Noms dans le ciel en exploration
+setting-tutorial = Tutoriel avant la prochaine partie
+bortle-1 = Bortle 1, site parfaitement noir
+bortle-2 = Bortle 2, site noir typique
+bortle-3 = Bortle 3, ciel rural
+bortle-4 = Bortle 4, entre campagne et banlieue
+bortle-5 = Bortle 5, ciel de banlieue
+bortle-6 = Bortle 6, banlieue lumineuse
+bortle-7 = Bortle 7, entre banlieue et ville
+bortle-8 = Bortle 8, ciel de ville
+bortle-9 = Bortle 9, centre-ville
+setting-on = activé
+setting-off = désactivé
+difficulty-easy = Facile
+difficulty-normal = Normal
+difficulty-hard = Difficile
+
+action-next = Commencer / Suivant
+action-back = Retour
+action-explore = Explorer
+action-hint = Indice
+action-recenter = Recentrer
+action-changeculture = Changer de culture céleste
+action-changelanguage = Changer de langue
+action-keybindings = Touches
+action-gyroscope = Gyroscope
+action-settings = Réglages
+action-mute = Couper le son
+action-dailychallenge = Défi du jour
+action-enterseed = Entrer un code de partie
+action-labels = Noms dans le ciel
+action-chart = Exporter une carte du ciel
+action-screenshot = Capture d'écran
+action-bookmark = Marquer la vue
+action-gotobookmark = Aller à une vue marquée
+action-tour = Visite guidée
+action-changetour = Changer de visite guidée
+action-measure = Mesurer un angle
+action-instrument = Champ d'un instrument
+action-matchinstrument = Zoomer sur le champ de l'instrument
+
+card-best-months = Meilleure période
+card-notable-stars = Étoiles remarquables
+card-deep-sky = Objets du ciel profond
+
+month-1 = janvier
+month-2 = février
+month-3 = mars
+month-4 = avril
+month-5 = mai
+month-6 = juin
+month-7 = juillet
+month-8 = août
+month-9 = septembre
+month-10 = octobre
+month-11 = novembre
+month-12 = décembre
+
+## Sky cultures
+
+culture-western = Occidentale
+culture-western-description = Les 88 constellations modernes de l'UAI
+culture-chinese = Chinoise
+culture-chinese-description = Astérismes des loges lunaires chinoises et du ciel boréal
+culture-norse = Nordique
+culture-norse-description = Noms d'étoiles tirés des Eddas et du folklore scandinave
+culture-polynesian = Polynésienne
+culture-polynesian-description = Étoiles de repère des navigateurs maoris et hawaïens
+
+## Constellations
+
+cons-andromeda = Andromède
+cons-cygnus = Cygne
+cons-ursa-major = Grande Ourse
+cons-orion = Orion
+cons-lyra = Lyre
+cons-cassiopeia = Cassiopée
+cons-aquila = Aigle
+cons-scorpio = Scorpion
+cons-libra = Balance
+cons-gemini = Gémeaux
+cons-leo = Lion
+cons-perseus = Persée
+cons-virgo = Vierge
+cons-hercules = Hercule
+cons-coma-berenices = Chevelure de Bérénice
+cons-canes-venatici = Chiens de chasse
+cons-crater = Coupe
+cons-canis-minor = Petit Chien
+cons-caelum = Burin
+cons-chamaeleon = Caméléon
+cons-cancer = Cancer
+cons-sculptor = Sculpteur
+cons-ara = Autel
+cons-cetus = Baleine
+cons-aries = Bélier
+cons-pyxis = Boussole
+cons-bootes = Bouvier
+cons-capricornus = Capricorne
+cons-cepheus = Céphée
+cons-auriga = Cocher
+cons-columba = Colombe
+cons-circinus = Compas
+cons-corona-australis = Couronne australe
+cons-corona-borealis = Couronne boréale
+cons-corvus = Corbeau
+cons-crux = Croix du Sud
+cons-delphinus = Dauphin
+cons-draco = Dragon
+cons-norma = Règle
+cons-sagitta = Flèche
+cons-fornax = Fourneau
+cons-canis-major = Grand Chien
+cons-grus = Grue
+cons-horologium = Horloge
+cons-hydra = Hydre
+cons-hydrus = Hydre mâle
+cons-indus = Indien
+cons-lacerta = Lézard
+cons-monoceros = Licorne
+cons-lepus = Lièvre
+cons-lupus = Loup
+cons-antlia = Machine pneumatique
+cons-microscopium = Microscope
+cons-musca = Mouche
+cons-octans = Octant
+cons-apus = Oiseau de paradis
+cons-ophiuchus = Serpentaire
+cons-pavo = Paon
+cons-pegasus = Pégase
+cons-pictor = Peintre
+cons-equuleus = Petit Cheval
+cons-leo-minor = Petit Lion
+cons-vulpecula = Petit Renard
+cons-ursa-minor = Petite Ourse
+cons-phoenix = Phénix
+cons-pisces = Poissons
+cons-piscis-austrinus = Poisson austral
+cons-volans = Poisson volant
+cons-reticulum = Réticule
+cons-sagittarius = Sagittaire
+cons-serpens = Serpent
+cons-sextans = Sextant
+cons-mensa = Table
+cons-taurus = Taureau
+cons-telescopium = Télescope
+cons-tucana = Toucan
+cons-triangulum = Triangle
+cons-triangulum-australe = Triangle austral
+cons-aquarius = Verseau
+cons-scutum = Écu de Sobieski
+cons-carina = Carène
+cons-centaurus = Centaure
+cons-dorado = Dorade
+cons-eridanus = Éridan
+cons-lynx = Lynx
+cons-puppis = Poupe
+cons-vela = Voiles
+cons-camelopardalis = Girafe
+
+## Sky culture figures
+
+cons-northern-dipper = Boisseau du Nord
+cons-northern-dipper-description = Le char céleste de l'Empereur, qui tourne autour du pôle
+cons-three-stars = Trois Étoiles
+cons-three-stars-description = Une loge lunaire nommée d'après les trois étoiles en son centre
+cons-heart = Cœur
+cons-heart-description = Le cœur du Dragon azur de l'Est
+cons-room = Chambre
+cons-room-description = Le ventre du Dragon azur
+cons-tail = Queue
+cons-tail-description = La queue du Dragon azur
+cons-weaving-girl = Tisserande
+cons-weaving-girl-description = La tisserande séparée du bouvier par la Voie lactée
+cons-river-drum = Tambour de la rivière
+cons-river-drum-description = Le tambour au bord de la rivière, demeure du bouvier
+cons-horn = Corne
+cons-horn-description = La corne du Dragon azur, première des loges lunaires
+cons-hairy-head = Tête chevelue
+cons-hairy-head-description = Une loge lunaire du Tigre blanc de l'Ouest
+cons-odin-s-wagon = Chariot d'Odin
+cons-odin-s-wagon-description = Le chariot du Père de tout, qui tourne autour du pôle
+cons-frigg-s-distaff = Quenouille de Frigg
+cons-frigg-s-distaff-description = Le fuseau de la reine d'Asgard
+cons-thiazi-s-eyes = Yeux de Thiazi
+cons-thiazi-s-eyes-description = Les yeux du géant Thiazi, jetés dans le ciel par Odin
+cons-wolf-s-jaw = Mâchoire du loup
+cons-wolf-s-jaw-description = La gueule ouverte d'un grand loup
+cons-aurvandil-s-toe = Orteil d'Aurvandil
+cons-aurvandil-s-toe-description = L'orteil gelé que Thor lança dans le ciel
+cons-matariki = Matariki
+cons-matariki-description = Son lever en hiver marque le nouvel an maori
+cons-tautoru = Tautoru
+cons-tautoru-description = Les trois étoiles alignées
+cons-maui-s-fishhook = Hameçon de Maui
+cons-maui-s-fishhook-description = L'hameçon avec lequel Maui tira les îles de la mer
+cons-mahutonga = Mahutonga
+cons-mahutonga-description = L'ancre du grand canoë céleste
+cons-na-hiku = Na Hiku
+cons-na-hiku-description = Les sept, guide du nord pour les navigateurs hawaïens
@@ -0,0 +1,124 @@
+// Stars as camera facing discs, one quad per star in a single mesh. Sizes,
+// fading, extinction and twinkling are worked out here from the magnitude,
+// so that nothing is updated per star on the CPU. The colour of each star
+// comes with its vertices
+
+#import bevy_pbr::mesh_functions::get_world_from_local
+#import bevy_pbr::mesh_view_bindings::view
+
+// Same layout as StarParams in starfield.rs
+struct StarParams {
+    zenith: vec3<f32>,
+    atmosphere: f32,
+    channel_extinction: vec3<f32>,
+    scale: f32,
+    max_size: f32,
+    easyness: f32,
+    limiting_magnitude: f32,
+    fade_range: f32,
+    extinction: f32,
+    twinkle_amplitude: f32,
+    max_twinkle_airmass: f32,
+    time: f32,
+    min_radius: f32,
+}
+
+@group(2) @binding(0) var<uniform> params: StarParams;
+
+struct Vertex {
+    @builtin(instance_index) instance_index: u32,
+    @builtin(vertex_index) vertex_index: u32,
+    @location(0) position: vec3<f32>,
+    @location(1) corner: vec2<f32>,
+    // Magnitude, and 1 for stars belonging to a constellation
+    @location(2) star: vec2<f32>,
+    // From the B-V colour index, brightest channel at 1
+    @location(3) star_color: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) corner: vec2<f32>,
+    @location(1) color: vec3<f32>,
+    @location(2) star_color: vec3<f32>,
+};
+
+// Relative air thickness from Kasten and Young, altitude in degrees
+fn airmass(altitude: f32) -> f32 {
+    return 1.0 / (sin(radians(altitude)) + 0.50572 * pow(altitude + 6.07995, -1.6364));
+}
+
+fn hidden() -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(2.0, 2.0, 2.0, 1.0);
+    out.corner = vec2<f32>(0.0);
+    out.color = vec3<f32>(0.0);
+    out.star_color = vec3<f32>(0.0);
+    return out;
+}
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var air = 1.0;
+    if params.atmosphere > 0.5 {
+        let altitude = degrees(asin(clamp(dot(normalize(vertex.position), params.zenith), -1.0, 1.0)));
+        if altitude < 0.0 {
+            return hidden();
+        }
+        air = airmass(altitude);
+    }
+
+    let magnitude = vertex.star.x + params.extinction * (air - 1.0);
+
+    var size = params.scale * pow(2.512, -magnitude * 0.5);
+    if vertex.star.y > 0.5 {
+        size *= params.easyness;
+    }
+    size = min(size, params.max_size * params.scale);
+    size *= clamp((params.limiting_magnitude - magnitude) / params.fade_range, 0.0, 1.0);
+
+    if params.atmosphere > 0.5 {
+        let phase = fract(sin(f32(vertex.vertex_index / 4u) * 12.9898) * 43758.545) * 6.2831853;
+        let flicker = 0.6 * sin(params.time * 11.0 + phase) + 0.4 * sin(params.time * 17.3 + 1.7 * phase);
+        size *= 1.0 + params.twinkle_amplitude * min(air, params.max_twinkle_airmass) * flicker;
+    }
+
+    let world_center = get_world_from_local(vertex.instance_index) * vec4<f32>(vertex.position, 1.0);
+    let view_center = view.view_from_world * world_center;
+    let depth = -view_center.z;
+
+    if size <= 0.0 || depth <= 0.0 {
+        return hidden();
+    }
+
+    // Stars smaller than a pixel are drawn at the minimum size and dimmed
+    // instead, so that faint ones do not flicker in and out between pixels
+    let pixels_per_unit = view.clip_from_view[1][1] * 0.5 * view.viewport.w / depth;
+    let radius = size * pixels_per_unit;
+    let drawn_radius = max(radius, params.min_radius);
+    let brightness = (radius / drawn_radius) * (radius / drawn_radius);
+
+    let offset = vertex.corner * drawn_radius / pixels_per_unit;
+
+    // Blue is scattered the most, red is kept at full brightness
+    let transmission = pow(vec3<f32>(10.0), -0.4 * params.channel_extinction * (air - 1.0));
+
+    var out: VertexOutput;
+    out.clip_position = view.clip_from_view * vec4<f32>(view_center.xy + offset, view_center.z, 1.0);
+    out.corner = vertex.corner;
+    out.color = transmission / transmission.r * brightness;
+    out.star_color = vertex.star_color;
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let distance = length(in.corner);
+    if distance > 1.0 {
+        discard;
+    }
+
+    // Additive blending, the alpha is left at 0
+    let coverage = 1.0 - smoothstep(0.6, 1.0, distance);
+    return vec4<f32>(in.star_color * in.color * coverage, 0.0);
+}
@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+use bevy::render::mesh::Indices;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::PrimitiveTopology;
+use std::f32::consts::TAU;
+
+use crate::Settings;
+
+use crate::observer::Observer;
+
+use crate::SKY_RADIUS;
+
+// Magnitudes lost per airmass in the visual band, on top of what is lost at the zenith
+pub const EXTINCTION: f32 = 0.25;
+// Red, green and blue, blue light is scattered the most
+pub const CHANNEL_EXTINCTION: [f32; 3] = [0.12, 0.22, 0.40];
+pub const TWINKLE_AMPLITUDE: f32 = 0.04;
+pub const MAX_TWINKLE_AIRMASS: f32 = 8.0;
+
+const GLOW_RADIUS: f32 = SKY_RADIUS * 0.95;
+const GLOW_SEGMENTS: usize = 64;
+// Altitude in degrees and intensity of the rings of the glow, brightest on the horizon
+const GLOW_RINGS: [(f32, f32); 4] = [(-10.0, 0.4), (0.0, 1.0), (8.0, 0.35), (25.0, 0.0)];
+
+#[derive(Component)]
+pub struct HorizonGlow;
+
+// Direction of the zenith in the sky at the observer's time, when the atmosphere is on
+pub fn zenith(settings: &Settings, observer: &Observer) -> Option<Vec3> {
+	settings.atmosphere.then(|| observer.horizon_to_sky(observer.time()) * Vec3::Z)
+}
+
+// Faint blue airglow at a dark site, orange street lights under a polluted one
+fn glow_color(settings: &Settings) -> LinearRgba {
+	let pollution = settings.light_pollution();
+	LinearRgba::rgb(0.03 + 0.20 * pollution, 0.035 + 0.14 * pollution, 0.06 + 0.07 * pollution)
+}
+
+// Band around the horizon, in the east / north / up frame of the observer
+fn glow_mesh() -> Mesh {
+	let mut positions : Vec<[f32; 3]> = vec![];
+	let mut colors : Vec<[f32; 4]> = vec![];
+	let mut indices : Vec<u32> = vec![];
+
+	for (altitude, intensity) in GLOW_RINGS {
+		let (sin, cos) = altitude.to_radians().sin_cos();
+		for segment in 0..=GLOW_SEGMENTS {
+			let azimuth = segment as f32 / GLOW_SEGMENTS as f32 * TAU;
+			positions.push([GLOW_RADIUS * cos * azimuth.sin(), GLOW_RADIUS * cos * azimuth.cos(), GLOW_RADIUS * sin]);
+			colors.push([intensity, intensity, intensity, 1.0]);
+		}
+	}
+
+	let row = GLOW_SEGMENTS as u32 + 1;
+	for ring in 0..GLOW_RINGS.len() as u32 - 1 {
+		for segment in 0..GLOW_SEGMENTS as u32 {
+			let below = ring * row + segment;
+			let above = below + row;
+			indices.extend([below, above, below + 1, below + 1, above, above + 1]);
+		}
+	}
+
+	Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::RENDER_WORLD)
+		.with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+		.with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+		.with_inserted_indices(Indices::U32(indices))
+}
+
+pub fn setup(
+	mut commands: Commands,
+	mut meshes: ResMut<Assets<Mesh>>,
+	mut materials: ResMut<Assets<StandardMaterial>>,
+	settings: Res<Settings>,
+) {
+	let glow_material = materials.add(StandardMaterial {
+		base_color: glow_color(&settings).into(),
+		unlit: true,
+		alpha_mode: AlphaMode::Add,
+		cull_mode: None,
+		..default()
+	});
+
+	commands.spawn((
+		PbrBundle {
+			mesh: meshes.add(glow_mesh()),
+			material: glow_material,
+			visibility: Visibility::Hidden,
+			..default()
+		},
+		HorizonGlow,
+	));
+}
+
+// Extinction and twinkling are done by the star shader, the glow follows the horizon as the sky turns
+pub fn horizon_glow(
+	settings: Res<Settings>,
+	observer: Res<Observer>,
+	mut materials: ResMut<Assets<StandardMaterial>>,
+	mut glow_query: Query<(&mut Transform, &mut Visibility, &Handle<StandardMaterial>), With<HorizonGlow>>,
+) {
+	if !settings.is_changed() && !settings.atmosphere {
+		return;
+	}
+
+	for (mut transform, mut visibility, glow_material) in glow_query.iter_mut() {
+		if !settings.atmosphere {
+			*visibility = Visibility::Hidden;
+			continue;
+		}
+
+		*visibility = Visibility::Inherited;
+		transform.rotation = observer.horizon_to_sky(observer.time());
+
+		if settings.is_changed() {
+			if let Some(material) = materials.get_mut(glow_material) {
+				material.base_color = glow_color(&settings).into();
+			}
+		}
+	}
+}
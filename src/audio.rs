@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+use bevy::audio::Volume;
+use bevy::asset::LoadState;
+use std::collections::HashMap;
+
+use crate::Settings;
+use crate::ActionInput;
+
+use crate::input::Action;
+
+// The one music entity, spawned once and kept across states
+#[derive(Component)]
+pub struct Music;
+
+#[derive(Event, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum SoundEffect {
+	Right,
+	Wrong,
+	Hint,
+	GameOver,
+}
+
+impl SoundEffect {
+	const ALL: [SoundEffect; 4] = [
+		SoundEffect::Right,
+		SoundEffect::Wrong,
+		SoundEffect::Hint,
+		SoundEffect::GameOver,
+	];
+
+	fn path(&self) -> &'static str {
+		match self {
+			SoundEffect::Right => "sounds/right.ogg",
+			SoundEffect::Wrong => "sounds/wrong.ogg",
+			SoundEffect::Hint => "sounds/hint.ogg",
+			SoundEffect::GameOver => "sounds/game_over.ogg",
+		}
+	}
+}
+
+// Effects are loaded up front so the first one is not late
+#[derive(Resource, Default)]
+pub struct SoundEffects {
+	handles: HashMap<SoundEffect, Handle<AudioSource>>,
+}
+
+pub fn setup(
+	mut commands: Commands,
+	asset_server: Res<AssetServer>,
+	settings: Res<Settings>,
+	mut sound_effects: ResMut<SoundEffects>,
+) {
+	commands.spawn((AudioBundle {
+		source: asset_server.load("Banjo.ogg"),
+		settings: PlaybackSettings::LOOP.with_volume(Volume::new(settings.music_level())),
+	}, Music));
+	info!("audio started");
+
+	for effect in SoundEffect::ALL {
+		sound_effects.handles.insert(effect, asset_server.load(effect.path()));
+	}
+}
+
+pub fn play_effects(
+	mut commands: Commands,
+	mut effect_events: EventReader<SoundEffect>,
+	asset_server: Res<AssetServer>,
+	sound_effects: Res<SoundEffects>,
+	settings: Res<Settings>,
+) {
+	for effect in effect_events.read() {
+		let level = settings.sfx_level();
+		if level <= 0.0 {
+			continue;
+		}
+
+		let Some(handle) = sound_effects.handles.get(effect) else {
+			continue;
+		};
+
+		// The effect files are not shipped, the game stays silent without them
+		if !matches!(asset_server.get_load_state(handle), Some(LoadState::Loaded)) {
+			continue;
+		}
+
+		commands.spawn(AudioBundle {
+			source: handle.clone(),
+			settings: PlaybackSettings::DESPAWN.with_volume(Volume::new(level)),
+		});
+	}
+}
+
+// The sink only exists once the music is loaded, the spawn volume covers before that
+pub fn music_volume(
+	settings: Res<Settings>,
+	sink_query: Query<&AudioSink, With<Music>>,
+) {
+	for sink in sink_query.iter() {
+		sink.set_volume(settings.music_level());
+	}
+}
+
+pub fn toggle_mute(
+	actions: ActionInput,
+	mut settings: ResMut<Settings>,
+) {
+	if actions.just_pressed(Action::Mute) {
+		settings.muted = !settings.muted;
+		settings.save();
+		info!("muted : {}", settings.muted);
+	}
+}
@@ -0,0 +1,114 @@
+use std::process::ExitCode;
+
+use astraea::validate_catalog;
+use astraea::validate_tours;
+use astraea::convert_catalog;
+use astraea::constellation_chart;
+use astraea::ChartProjection;
+
+// Tools for the star data :
+//   astraea-data [check] [stars.json] [constellations.json]
+//     checks the star catalog, the constellation figures and the tours before they are embedded in the game
+//   astraea-data convert <stars.json|stars.csv> <stars.bin> [faintest magnitude]
+//     writes a binary catalog, to be loaded with --catalog
+//   astraea-data chart <constellation> [chart.svg] [--gnomonic|--stereographic]
+//     prints a constellation and its surroundings, the projection is picked from the field otherwise
+fn main() -> ExitCode {
+	let args : Vec<String> = std::env::args().skip(1).collect();
+
+	match args.first().map(String::as_str) {
+		Some("convert") => convert(&args[1..]),
+		Some("chart") => chart(&args[1..]),
+		Some("check") => check(&args[1..]),
+		_ => check(&args),
+	}
+}
+
+fn check(args: &[String]) -> ExitCode {
+	let stars_path = args.first().map(String::as_str).unwrap_or("data/stars.json");
+	let constellations_path = args.get(1).map(String::as_str).unwrap_or("data/constellations.json");
+
+	let read = |path: &str| std::fs::read_to_string(path).map_err(|error| eprintln!("{} : {}", path, error));
+
+	let (Ok(stars_json), Ok(constellations_json)) = (read(stars_path), read(constellations_path)) else {
+		return ExitCode::from(2);
+	};
+
+	let report = validate_catalog(&stars_json, &constellations_json);
+	println!("{}", report);
+
+	// Tours are embedded, their constellations are looked up in the given figures
+	let tour_errors = validate_tours(&constellations_json);
+	for error in tour_errors.iter() {
+		println!("error : {}", error);
+	}
+
+	if report.is_valid() && tour_errors.is_empty() {
+		ExitCode::SUCCESS
+	} else {
+		ExitCode::FAILURE
+	}
+}
+
+fn convert(args: &[String]) -> ExitCode {
+	let (Some(input), Some(output)) = (args.first(), args.get(1)) else {
+		eprintln!("usage : astraea-data convert <stars.json|stars.csv> <stars.bin> [faintest magnitude]");
+		return ExitCode::from(2);
+	};
+
+	let faintest = match args.get(2).map(|magnitude| magnitude.parse::<f32>()) {
+		Some(Ok(magnitude)) => Some(magnitude),
+		Some(Err(_)) => {
+			eprintln!("the faintest magnitude should be a number");
+			return ExitCode::from(2);
+		}
+		None => None,
+	};
+
+	match convert_catalog(input, output, faintest) {
+		Ok((count, skipped)) => {
+			println!("{} stars written to {}", count, output);
+			if skipped > 0 {
+				println!("{} rows skipped, without coordinates or magnitude", skipped);
+			}
+			ExitCode::SUCCESS
+		}
+		Err(error) => {
+			eprintln!("{}", error);
+			ExitCode::FAILURE
+		}
+	}
+}
+
+fn chart(args: &[String]) -> ExitCode {
+	let projection = if args.iter().any(|arg| arg == "--gnomonic") {
+		Some(ChartProjection::Gnomonic)
+	} else if args.iter().any(|arg| arg == "--stereographic") {
+		Some(ChartProjection::Stereographic)
+	} else {
+		None
+	};
+	let mut names = args.iter().filter(|arg| !arg.starts_with("--"));
+
+	let Some(name) = names.next() else {
+		eprintln!("usage : astraea-data chart <constellation> [chart.svg] [--gnomonic|--stereographic]");
+		return ExitCode::from(2);
+	};
+	let output = names.next().cloned().unwrap_or_else(|| format!("{}.svg", name.to_lowercase().replace(' ', "-")));
+
+	let Some(svg) = constellation_chart(name, projection) else {
+		eprintln!("no constellation named {}", name);
+		return ExitCode::FAILURE;
+	};
+
+	match std::fs::write(&output, svg) {
+		Ok(()) => {
+			println!("chart written to {}", output);
+			ExitCode::SUCCESS
+		}
+		Err(error) => {
+			eprintln!("{} : {}", output, error);
+			ExitCode::from(2)
+		}
+	}
+}
@@ -0,0 +1,171 @@
+use bevy::prelude::*;
+
+use crate::GameState;
+use crate::KeyBindingsMenu;
+use crate::Locale;
+use crate::Localized;
+use crate::InputMap;
+
+use crate::input::Action;
+use crate::input::BINDABLE_KEYS;
+
+#[derive(Component)]
+pub struct BindingRow(usize);
+
+#[derive(Component)]
+pub struct BindingsHelp;
+
+#[derive(Resource, Default)]
+pub struct BindingsMenu {
+	selected: usize,
+	waiting: bool,
+}
+
+pub fn setup(
+	mut commands: Commands,
+	mut menu: ResMut<BindingsMenu>,
+) {
+	*menu = BindingsMenu::default();
+
+	let container_node = NodeBundle {
+		style: Style {
+			width: Val::Percent(100.0),
+			height: Val::Percent(100.0),
+			flex_direction: FlexDirection::Column,
+			justify_content: JustifyContent::Center,
+			align_items: AlignItems::Center,
+			..default()
+		},
+		..default()
+	};
+
+	let container = commands.spawn((container_node, KeyBindingsMenu)).id();
+
+	let title_text_node = TextBundle::from_section(
+		"",
+		TextStyle {
+			font_size: 50.0,
+			color: Color::WHITE,
+			// font: asset_server.load("fonts/FiraSans-Bold.ttf"), // Load font if needed
+			..default()
+		},
+	).with_style(Style {
+		margin: UiRect::bottom(Val::Px(20.0)),
+		..default()
+	});
+
+	let title_text = commands.spawn((title_text_node, KeyBindingsMenu, Localized::new("bindings-title"))).id();
+	commands.entity(container).push_children(&[title_text]);
+
+	for index in 0..Action::ALL.len() {
+		let row_text_node = TextBundle::from_section(
+			"",
+			TextStyle {
+				font_size: 25.0,
+				color: Color::WHITE,
+				// font: asset_server.load("fonts/FiraSans-Regular.ttf"), // Load font if needed
+				..default()
+			},
+		);
+
+		let row_text = commands.spawn((row_text_node, KeyBindingsMenu, BindingRow(index))).id();
+		commands.entity(container).push_children(&[row_text]);
+	}
+
+	let help_text_node = TextBundle::from_section(
+		"",
+		TextStyle {
+			font_size: 20.0,
+			color: Color::srgb(0.4,0.4,0.4),
+			// font: asset_server.load("fonts/FiraSans-Regular.ttf"), // Load font if needed
+			..default()
+		},
+	).with_style(Style {
+		margin: UiRect::top(Val::Px(20.0)),
+		..default()
+	});
+
+	let help_text = commands.spawn((help_text_node, KeyBindingsMenu, BindingsHelp)).id();
+	commands.entity(container).push_children(&[help_text]);
+}
+
+// The bindings are changed here, so the back action is read without ActionInput,
+// Select leaving as well for a controller opening the screen from the menu
+pub fn player_interact(
+	keys: Res<ButtonInput<KeyCode>>,
+	gamepads: Res<Gamepads>,
+	gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+	mut input_map: ResMut<InputMap>,
+	mut menu: ResMut<BindingsMenu>,
+	mut game_state: ResMut<NextState<GameState>>,
+) {
+	let back = keys.just_pressed(input_map.key(Action::Back))
+		|| Action::Back.gamepad_button().is_some_and(|button_type| {
+			gamepads.iter().any(|gamepad| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button_type)))
+		});
+
+	if menu.waiting {
+		// The back key leaves the binding as it was
+		if back {
+			menu.waiting = false;
+			return;
+		}
+
+		for key in BINDABLE_KEYS {
+			if keys.just_pressed(key) {
+				input_map.rebind(Action::ALL[menu.selected], key);
+				input_map.save();
+				menu.waiting = false;
+				return;
+			}
+		}
+		return;
+	}
+
+	if back {
+		game_state.set(GameState::Start);
+		return;
+	}
+
+	if keys.just_pressed(KeyCode::ArrowDown) {
+		menu.selected = (menu.selected + 1) % Action::ALL.len();
+	}
+
+	if keys.just_pressed(KeyCode::ArrowUp) {
+		menu.selected = (menu.selected + Action::ALL.len() - 1) % Action::ALL.len();
+	}
+
+	if keys.just_pressed(KeyCode::Enter) {
+		menu.waiting = true;
+	}
+}
+
+pub fn ui_labels(
+	menu: Res<BindingsMenu>,
+	input_map: Res<InputMap>,
+	locale: Res<Locale>,
+	mut row_query: Query<(&mut Text, &BindingRow), Without<BindingsHelp>>,
+	mut help_query: Query<&mut Text, (With<BindingsHelp>, Without<BindingRow>)>,
+) {
+	for (mut text, row) in row_query.iter_mut() {
+		let action = Action::ALL[row.0];
+		let selected = row.0 == menu.selected;
+
+		let key = if selected && menu.waiting {
+			locale.text_with("bindings-waiting", &[("key".into(), input_map.key_name(Action::Back))])
+		} else {
+			input_map.key_name(action)
+		};
+
+		text.sections[0].value = format!("{} : {}", locale.text(&action.locale_key()), key);
+		text.sections[0].style.color = if selected {
+			Color::WHITE
+		} else {
+			Color::srgb(0.4,0.4,0.4)
+		};
+	}
+
+	if let Ok(mut text) = help_query.get_single_mut() {
+		text.sections[0].value = locale.text_with("bindings-help", &[("key".into(), input_map.key_name(Action::Back))]);
+	}
+}
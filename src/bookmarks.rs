@@ -0,0 +1,297 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::Player;
+use crate::GameState;
+use crate::ConstellationModel;
+use crate::Sky;
+use crate::Locale;
+use crate::ActionInput;
+use crate::Settings;
+
+use crate::input::Action;
+use crate::observer::Observer;
+use crate::chart::celestial_coordinates;
+use crate::settings;
+
+use crate::celestial_to_cartesian;
+
+use crate::MIN_FOV;
+use crate::MAX_FOV;
+
+// Field of view of links naming a constellation, in degrees
+const LINK_FOV: f32 = 60.0;
+
+// A view of the sky to come back to
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Bookmark {
+	pub name: String,
+	// Centre of the screen, right ascension in hours and declination in degrees
+	pub rah: f64,
+	pub dec: f64,
+	// Vertical field of view, and turn from north up, in degrees
+	pub fov: f32,
+	#[serde(default)]
+	pub roll: f32,
+	// Place and time of the sky, kept when the atmosphere is on
+	#[serde(default)]
+	pub observer: Option<(f64, f64)>,
+	#[serde(default)]
+	pub time: Option<f64>,
+}
+
+// Up on screen when the camera has no roll, towards the celestial north pole
+fn north_up(forward: Vec3) -> Vec3 {
+	(Vec3::Y - forward * forward.y).try_normalize().unwrap_or(Vec3::Z)
+}
+
+// Camera looking at a right ascension and declination, turned by a roll in degrees from north up
+pub fn view_rotation(rah: f64, dec: f64, roll: f32) -> Quat {
+	let forward = celestial_to_cartesian(rah, dec);
+	let north_up = Transform::IDENTITY.looking_to(forward, north_up(forward)).rotation;
+	Quat::from_axis_angle(forward, roll.to_radians()) * north_up
+}
+
+impl Bookmark {
+	pub fn from_camera(name: &str, rotation: Quat, fov: f32) -> Bookmark {
+		let forward = rotation * Vec3::NEG_Z;
+		let up = rotation * Vec3::Y;
+		let reference = north_up(forward);
+		let (rah, dec) = celestial_coordinates(forward);
+
+		Bookmark {
+			name: name.into(),
+			rah: rah as f64,
+			dec: dec as f64,
+			fov: fov.to_degrees(),
+			roll: forward.dot(reference.cross(up)).atan2(reference.dot(up)).to_degrees(),
+			observer: None,
+			time: None,
+		}
+	}
+
+	pub fn rotation(&self) -> Quat {
+		view_rotation(self.rah, self.dec, self.roll)
+	}
+
+	// For links : ra=5.919&dec=7.41&fov=40, then the roll, the place and the time when there are some
+	pub fn to_fragment(&self) -> String {
+		let mut fragment = format!("ra={:.4}&dec={:.3}&fov={:.2}", self.rah, self.dec, self.fov);
+		if self.roll.abs() > 0.05 {
+			fragment += &format!("&roll={:.1}", self.roll);
+		}
+		if let Some((latitude, longitude)) = self.observer {
+			fragment += &format!("&lat={:.2}&lon={:.2}", latitude, longitude);
+		}
+		if let Some(time) = self.time {
+			fragment += &format!("&t={:.0}", time);
+		}
+		fragment
+	}
+
+	pub fn from_fragment(fragment: &str) -> Option<Bookmark> {
+		let value = |key: &str| fragment.trim_start_matches('#').split('&')
+			.filter_map(|pair| pair.split_once('='))
+			.find(|(name, _)| *name == key)
+			.and_then(|(_, value)| value.parse::<f64>().ok())
+			.filter(|value| value.is_finite());
+
+		let (rah, dec) = (value("ra")?, value("dec")?);
+		if !(-90.0..=90.0).contains(&dec) {
+			return None;
+		}
+
+		Some(Bookmark {
+			name: String::new(),
+			rah: rah.rem_euclid(24.0),
+			dec,
+			fov: value("fov").map(|fov| fov as f32).unwrap_or(LINK_FOV),
+			roll: value("roll").unwrap_or(0.0) as f32,
+			observer: value("lat").zip(value("lon")),
+			time: value("t"),
+		})
+	}
+
+	// Links may also just name a constellation of the current sky culture, #Orion
+	fn from_constellation(sky: &Sky, name: &str) -> Option<Bookmark> {
+		let constellation = sky.content.iter().find(|constellation| constellation.name.eq_ignore_ascii_case(name.trim()))?;
+		let center = constellation.stars.iter().map(|star| celestial_to_cartesian(star.rah, star.dec)).sum::<Vec3>().try_normalize()?;
+		let (rah, dec) = celestial_coordinates(center);
+
+		Some(Bookmark {
+			name: constellation.name.clone(),
+			rah: rah as f64,
+			dec: dec as f64,
+			fov: LINK_FOV,
+			roll: 0.0,
+			observer: None,
+			time: None,
+		})
+	}
+}
+
+#[derive(Resource, Default)]
+pub struct Bookmarks {
+	pub views: Vec<Bookmark>,
+	// Bookmark the next press goes to
+	next: usize,
+	// The player's own place while a bookmark shows the sky from another one
+	home: Option<(f64, f64)>,
+}
+
+impl Bookmarks {
+	pub fn load() -> Self {
+		let views : Vec<Bookmark> = settings::load("bookmarks").unwrap_or_default();
+		Bookmarks {
+			next: views.len().saturating_sub(1),
+			views,
+			home: None,
+		}
+	}
+
+	// The place and time go with the view, a bookmark without them brings the
+	// player's own place and the live sky back
+	pub fn move_observer(&mut self, bookmark: &Bookmark, observer: &mut Observer) {
+		let place = bookmark.observer.or_else(|| self.home.take());
+		if let Some((latitude, longitude)) = place {
+			if bookmark.observer.is_some() {
+				self.home.get_or_insert((observer.latitude, observer.longitude));
+			}
+			observer.latitude = latitude;
+			observer.longitude = longitude;
+		}
+		observer.time = bookmark.time;
+	}
+}
+
+fn apply_bookmark(bookmark: &Bookmark, bookmarks: &mut Bookmarks, player: &mut Player, observer: &mut Observer) {
+	player.target_rotation = Some(bookmark.rotation());
+	player.target_fov = Some(bookmark.fov.to_radians().clamp(MIN_FOV, MAX_FOV));
+	bookmarks.move_observer(bookmark, observer);
+}
+
+// Named after the constellation closest to the centre of the screen
+pub fn save_view(
+	actions: ActionInput,
+	settings: Res<Settings>,
+	observer: Res<Observer>,
+	locale: Res<Locale>,
+	mut bookmarks: ResMut<Bookmarks>,
+	camera_query: Query<(&Transform, &Projection), With<Player>>,
+	constellation_query: Query<&ConstellationModel>,
+) {
+	if !actions.just_pressed(Action::Bookmark) {
+		return;
+	}
+
+	let Ok((transform, Projection::Perspective(perspective))) = camera_query.get_single() else {
+		return;
+	};
+
+	let forward = *transform.forward();
+	let name = constellation_query.iter()
+		.max_by(|a, b| a.center.normalize().dot(forward).total_cmp(&b.center.normalize().dot(forward)))
+		.map(|constellation| locale.constellation(&constellation.name))
+		.unwrap_or_else(|| "Astraea".into());
+
+	let mut bookmark = Bookmark::from_camera(&name, transform.rotation, perspective.fov);
+	if settings.atmosphere {
+		bookmark.observer = Some((observer.latitude, observer.longitude));
+		bookmark.time = Some(observer.time());
+	}
+
+	info!("view bookmarked as {} : {}", bookmark.name, bookmark.to_fragment());
+	bookmarks.views.push(bookmark);
+	bookmarks.next = bookmarks.views.len() - 1;
+	settings::save("bookmarks", &bookmarks.views);
+}
+
+// Goes through the bookmarks, from the last one saved
+pub fn restore_view(
+	actions: ActionInput,
+	mut bookmarks: ResMut<Bookmarks>,
+	mut observer: ResMut<Observer>,
+	mut player_query: Query<&mut Player>,
+) {
+	if !actions.just_pressed(Action::GoToBookmark) || bookmarks.views.is_empty() {
+		return;
+	}
+
+	let Ok(mut player) = player_query.get_single_mut() else {
+		return;
+	};
+
+	let index = bookmarks.next.min(bookmarks.views.len() - 1);
+	let bookmark = bookmarks.views[index].clone();
+	info!("going to bookmark {}", bookmark.name);
+
+	apply_bookmark(&bookmark, &mut bookmarks, &mut player, &mut observer);
+	bookmarks.next = (index + bookmarks.views.len() - 1) % bookmarks.views.len();
+}
+
+// A link with a view opens explore mode looking at it
+pub fn open_link(
+	sky: Res<Sky>,
+	mut bookmarks: ResMut<Bookmarks>,
+	mut observer: ResMut<Observer>,
+	mut player_query: Query<&mut Player>,
+	mut game_state: ResMut<NextState<GameState>>,
+) {
+	let Some(link) = read_link() else {
+		return;
+	};
+
+	let Some(bookmark) = Bookmark::from_fragment(&link).or_else(|| Bookmark::from_constellation(&sky, &link)) else {
+		warn!("no view in link {}", link);
+		return;
+	};
+
+	let Ok(mut player) = player_query.get_single_mut() else {
+		return;
+	};
+
+	apply_bookmark(&bookmark, &mut bookmarks, &mut player, &mut observer);
+	game_state.set(GameState::Explo);
+}
+
+// --view <link> on desktop
+#[cfg(not(target_arch = "wasm32"))]
+fn read_link() -> Option<String> {
+	std::env::args().skip_while(|arg| arg != "--view").nth(1)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_link() -> Option<String> {
+	crate::web::fragment_get()
+}
+
+// The address follows the view, to be copied from the browser at any time
+#[cfg(target_arch = "wasm32")]
+pub fn update_link(
+	time: Res<Time>,
+	settings: Res<Settings>,
+	observer: Res<Observer>,
+	camera_query: Query<(&Transform, &Projection), With<Player>>,
+	mut last_link: Local<(String, f32)>,
+) {
+	let Ok((transform, Projection::Perspective(perspective))) = camera_query.get_single() else {
+		return;
+	};
+
+	// Browsers throttle history updates
+	if time.elapsed_seconds() - last_link.1 < 0.5 {
+		return;
+	}
+
+	let mut bookmark = Bookmark::from_camera("", transform.rotation, perspective.fov);
+	if settings.atmosphere {
+		bookmark.observer = Some((observer.latitude, observer.longitude));
+		bookmark.time = observer.time;
+	}
+
+	let link = bookmark.to_fragment();
+	if link != last_link.0 {
+		crate::web::fragment_set(&link);
+		*last_link = (link, time.elapsed_seconds());
+	}
+}
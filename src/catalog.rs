@@ -0,0 +1,374 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::StarData;
+use crate::Constellation;
+
+// Figure stars are copied by hand from the catalog, a few arc seconds of rounding is expected
+const POSITION_TOLERANCE: f64 = 0.1;
+// The centre is where the camera looks, it should at least be near the figure
+const CENTRE_TOLERANCE: f64 = 10.0;
+// Sirius is -1.46, the catalog stops around 8
+const MIN_MAGNITUDE: f32 = -2.0;
+const MAX_MAGNITUDE: f32 = 9.0;
+
+// Three letter abbreviations used by the Yale catalog in bfID
+const GREEK_LETTERS: [(&str, char); 24] = [
+	("Alp", 'α'), ("Bet", 'β'), ("Gam", 'γ'), ("Del", 'δ'),
+	("Eps", 'ε'), ("Zet", 'ζ'), ("Eta", 'η'), ("The", 'θ'),
+	("Iot", 'ι'), ("Kap", 'κ'), ("Lam", 'λ'), ("Mu", 'μ'),
+	("Nu", 'ν'), ("Xi", 'ξ'), ("Omi", 'ο'), ("Pi", 'π'),
+	("Rho", 'ρ'), ("Sig", 'σ'), ("Tau", 'τ'), ("Ups", 'υ'),
+	("Phi", 'φ'), ("Chi", 'χ'), ("Psi", 'ψ'), ("Ome", 'ω'),
+];
+
+const SUPERSCRIPTS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+#[derive(Default, Debug)]
+pub struct CatalogReport {
+	pub star_count: usize,
+	pub constellation_count: usize,
+	pub errors: Vec<String>,
+	pub warnings: Vec<String>,
+}
+
+impl CatalogReport {
+	pub fn is_valid(&self) -> bool {
+		self.errors.is_empty()
+	}
+
+	fn error(&mut self, message: String) {
+		self.errors.push(message);
+	}
+
+	fn warning(&mut self, message: String) {
+		self.warnings.push(message);
+	}
+}
+
+impl fmt::Display for CatalogReport {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for error in &self.errors {
+			writeln!(f, "error : {}", error)?;
+		}
+		for warning in &self.warnings {
+			writeln!(f, "warning : {}", warning)?;
+		}
+		write!(f, "{} stars, {} constellations : {} errors, {} warnings",
+			self.star_count, self.constellation_count, self.errors.len(), self.warnings.len())
+	}
+}
+
+// Right ascension in hours, from "00h 05m 09.9s"
+pub fn parse_ra(text: &str) -> Option<f64> {
+	let parts = text.split(['h', 'm', 's'])
+		.map(str::trim)
+		.filter(|part| !part.is_empty())
+		.map(|part| part.parse::<f64>().ok())
+		.collect::<Option<Vec<f64>>>()?;
+
+	let [hours, minutes, seconds] = parts[..] else {
+		return None;
+	};
+
+	if minutes >= 60.0 || seconds >= 60.0 {
+		return None;
+	}
+
+	Some(hours + minutes / 60.0 + seconds / 3600.0)
+}
+
+// Declination in degrees, from "-00° 30′ 11″". The sign applies to the whole
+// angle, minutes and seconds of a southern star pull it further south
+pub fn parse_dec(text: &str) -> Option<f64> {
+	let text = text.trim();
+	let negative = text.starts_with('-');
+
+	let parts = text.trim_start_matches(['+', '-'])
+		.split(['°', '′', '″'])
+		.map(str::trim)
+		.filter(|part| !part.is_empty())
+		.map(|part| part.parse::<f64>().ok())
+		.collect::<Option<Vec<f64>>>()?;
+
+	let [degrees, minutes, seconds] = parts[..] else {
+		return None;
+	};
+
+	if minutes >= 60.0 || seconds >= 60.0 {
+		return None;
+	}
+
+	let dec = degrees + minutes / 60.0 + seconds / 3600.0;
+	Some(if negative { -dec } else { dec })
+}
+
+// Great circle distance in degrees
+fn angular_distance((rah1, dec1): (f64, f64), (rah2, dec2): (f64, f64)) -> f64 {
+	let (ra1, ra2) = ((rah1 * 15.0).to_radians(), (rah2 * 15.0).to_radians());
+	let (dec1, dec2) = (dec1.to_radians(), dec2.to_radians());
+
+	let cos = dec1.sin() * dec2.sin() + dec1.cos() * dec2.cos() * (ra1 - ra2).cos();
+	cos.clamp(-1.0, 1.0).acos().to_degrees()
+}
+
+// A bfID such as "21Alp And", "57Gam1And", "1Pi 3Ori" or "61 Cyg"
+struct Designation {
+	flamsteed: Option<String>,
+	bayer: Option<char>,
+	component: Option<char>,
+	constellation: String,
+}
+
+fn parse_designation(bfid: &str) -> Option<Designation> {
+	let bfid = bfid.trim();
+	let split = bfid.char_indices().rev().nth(2)?.0;
+	let (rest, constellation) = bfid.split_at(split);
+
+	if !constellation.chars().all(|c| c.is_ascii_alphabetic()) {
+		return None;
+	}
+
+	let flamsteed : String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+	let rest = &rest[flamsteed.len()..];
+	let letters : String = rest.chars().take_while(|c| c.is_ascii_alphabetic()).collect();
+	let rest = rest[letters.len()..].trim();
+
+	let bayer = match letters.as_str() {
+		"" => None,
+		letters => Some(GREEK_LETTERS.iter().find(|(name, _)| *name == letters)?.1),
+	};
+
+	let component = match rest {
+		"" => None,
+		rest if rest.len() == 1 && bayer.is_some() => {
+			let digit = rest.chars().next()?.to_digit(10)?;
+			Some(SUPERSCRIPTS[digit as usize])
+		}
+		_ => return None,
+	};
+
+	if flamsteed.is_empty() && bayer.is_none() {
+		return None;
+	}
+
+	Some(Designation {
+		flamsteed: (!flamsteed.is_empty()).then_some(flamsteed),
+		bayer,
+		component,
+		constellation: constellation.to_string(),
+	})
+}
+
+impl Designation {
+	fn matches(&self, star: &StarData) -> bool {
+		if star.constellation.as_deref() != Some(self.constellation.as_str()) {
+			return false;
+		}
+
+		if let Some(flamsteed) = &self.flamsteed {
+			if star.f.as_ref() != Some(flamsteed) {
+				return false;
+			}
+		}
+
+		if let Some(bayer) = self.bayer {
+			let mut letters = star.bayer_designation.as_deref().unwrap_or("").chars();
+			if letters.next() != Some(bayer) {
+				return false;
+			}
+			if self.component.is_some() && letters.next() != self.component {
+				return false;
+			}
+		}
+
+		true
+	}
+}
+
+// Checks the hand edited star and constellation files, everything the game
+// would unwrap or index blindly is an error, the rest a warning
+pub fn validate_catalog(stars_json: &str, constellations_json: &str) -> CatalogReport {
+	let mut report = CatalogReport::default();
+
+	let stars : Vec<StarData> = match serde_json::from_str(stars_json) {
+		Ok(stars) => stars,
+		Err(error) => {
+			report.error(format!("stars : {}", error));
+			return report;
+		}
+	};
+
+	let constellations : Vec<Constellation> = match serde_json::from_str(constellations_json) {
+		Ok(constellations) => constellations,
+		Err(error) => {
+			report.error(format!("constellations : {}", error));
+			return report;
+		}
+	};
+
+	report.star_count = stars.len();
+	report.constellation_count = constellations.len();
+
+	let positions = check_stars(&stars, &mut report);
+
+	let mut names = HashSet::new();
+	for constellation in &constellations {
+		if !names.insert(constellation.name.as_str()) {
+			report.error(format!("{} : duplicate constellation name", constellation.name));
+		}
+		check_constellation(constellation, &stars, &positions, &mut report);
+	}
+
+	report
+}
+
+// Positions of the stars that parse, by catalog index
+fn check_stars(stars: &[StarData], report: &mut CatalogReport) -> HashMap<usize, (f64, f64)> {
+	let mut positions = HashMap::new();
+	let mut hr_numbers = HashSet::new();
+
+	for (index, star) in stars.iter().enumerate() {
+		let label = format!("star {} (HR {})", index, star.hr);
+
+		match star.hr.parse::<usize>() {
+			Ok(hr) if !hr_numbers.insert(hr) => report.error(format!("{} : duplicate HR number", label)),
+			Ok(_) => {}
+			Err(_) => report.error(format!("{} : HR is not a number", label)),
+		}
+
+		let rah = match parse_ra(&star.ra) {
+			Some(rah) if (0.0..24.0).contains(&rah) => Some(rah),
+			Some(_) => {
+				report.error(format!("{} : right ascension {} is out of 0h-24h", label, star.ra));
+				None
+			}
+			None => {
+				report.error(format!("{} : unreadable right ascension {:?}", label, star.ra));
+				None
+			}
+		};
+
+		let dec = match parse_dec(&star.dec) {
+			Some(dec) if (-90.0..=90.0).contains(&dec) => Some(dec),
+			Some(_) => {
+				report.error(format!("{} : declination {} is out of -90°-90°", label, star.dec));
+				None
+			}
+			None => {
+				report.error(format!("{} : unreadable declination {:?}", label, star.dec));
+				None
+			}
+		};
+
+		match star.v.trim().parse::<f32>() {
+			Ok(magnitude) if (MIN_MAGNITUDE..=MAX_MAGNITUDE).contains(&magnitude) => {}
+			Ok(magnitude) => report.error(format!("{} : magnitude {} is out of {}-{}", label, magnitude, MIN_MAGNITUDE, MAX_MAGNITUDE)),
+			Err(_) => report.error(format!("{} : unreadable magnitude {:?}", label, star.v)),
+		}
+
+		if let (Some(rah), Some(dec)) = (rah, dec) {
+			positions.insert(index, (rah, dec));
+		}
+	}
+
+	positions
+}
+
+fn check_constellation(
+	constellation: &Constellation,
+	stars: &[StarData],
+	positions: &HashMap<usize, (f64, f64)>,
+	report: &mut CatalogReport,
+) {
+	let name = &constellation.name;
+	let centre = (constellation.rah, constellation.dec);
+
+	if !(0.0..24.0).contains(&constellation.rah) || !(-90.0..=90.0).contains(&constellation.dec) {
+		report.error(format!("{} : centre RAh {} DEd {} is off the sky", name, constellation.rah, constellation.dec));
+	}
+
+	if constellation.stars.is_empty() {
+		report.error(format!("{} : no stars", name));
+		return;
+	}
+
+	if constellation.lines.is_empty() {
+		report.error(format!("{} : no lines", name));
+	}
+
+	let closest = constellation.stars.iter()
+		.map(|star| angular_distance(centre, (star.rah, star.dec)))
+		.fold(f64::MAX, f64::min);
+
+	if closest > CENTRE_TOLERANCE {
+		report.warning(format!("{} : centre is {:.1}° away from its closest star", name, closest));
+	}
+
+	for (index, star) in constellation.stars.iter().enumerate() {
+		let label = format!("{} star {} ({})", name, index, star.bfid);
+
+		if star.id != index {
+			report.warning(format!("{} : id is {}, lines use the position in the list", label, star.id));
+		}
+
+		if !(0.0..24.0).contains(&star.rah) || !(-90.0..=90.0).contains(&star.dec) {
+			report.error(format!("{} : RAh {} DEd {} is off the sky", label, star.rah, star.dec));
+			continue;
+		}
+
+		let position = (star.rah, star.dec);
+
+		// Fainter figure stars have no designation, they can only be found by position
+		let candidates : Vec<usize> = if star.bfid.trim().is_empty() {
+			(0..stars.len()).collect()
+		} else {
+			let Some(designation) = parse_designation(&star.bfid) else {
+				report.error(format!("{} : unreadable bfID", label));
+				continue;
+			};
+
+			let candidates : Vec<usize> = (0..stars.len()).filter(|&index| designation.matches(&stars[index])).collect();
+			if candidates.is_empty() {
+				report.error(format!("{} : no catalog star with this designation", label));
+				continue;
+			}
+			candidates
+		};
+
+		let distance = candidates.iter()
+			.filter_map(|index| positions.get(index))
+			.map(|catalog_position| angular_distance(position, *catalog_position))
+			.fold(f64::MAX, f64::min);
+
+		if distance > POSITION_TOLERANCE {
+			if star.bfid.trim().is_empty() {
+				report.warning(format!("{} : no catalog star at this position", label));
+			} else {
+				report.error(format!("{} : {:.2}° away from the catalog star", label, distance));
+			}
+		}
+	}
+
+	let mut used = vec![false; constellation.stars.len()];
+
+	for line in &constellation.lines {
+		for &star_index in line {
+			match used.get_mut(star_index as usize) {
+				Some(used) => *used = true,
+				None => report.error(format!("{} : line {:?} uses star {} but there are {} stars", name, line, star_index, constellation.stars.len())),
+			}
+		}
+
+		if line[0] == line[1] {
+			report.warning(format!("{} : line {:?} joins a star to itself", name, line));
+		}
+	}
+
+	for (index, used) in used.iter().enumerate() {
+		if !used {
+			report.warning(format!("{} : star {} ({}) is in no line", name, index, constellation.stars[index].bfid));
+		}
+	}
+}
@@ -0,0 +1,326 @@
+use bevy::prelude::*;
+use std::fmt::Write;
+
+use crate::Player;
+use crate::Sky;
+use crate::Locale;
+use crate::ActionInput;
+use crate::Settings;
+use crate::Constellation;
+
+use crate::input::Action;
+use crate::seed::unix_time;
+
+use crate::celestial_to_cartesian;
+use crate::star_catalog;
+
+// Charts are drawn 1000 units wide, a constellation chart fits an A4 page in landscape
+const CHART_WIDTH: f32 = 1000.0;
+const PAGE_ASPECT: f32 = 1.414;
+// Wider fields are drawn stereographic, gnomonic charts stretch too much past it
+const MAX_GNOMONIC_FOV: f32 = 90.0;
+// Room left around a constellation, and the narrowest field for the small ones
+const CONSTELLATION_MARGIN: f32 = 1.3;
+const MIN_CONSTELLATION_FOV: f32 = 20.0;
+// Printed charts stop at what the eye sees under a dark sky
+const FAINTEST_PRINTED: f32 = 6.5;
+// Star names written on the chart
+const NAMED_MAGNITUDE: f32 = 2.5;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ChartProjection {
+	// Great circles stay straight, handy to star-hop along a ruler
+	Gnomonic,
+	// Shapes are kept, for wide fields
+	Stereographic,
+}
+
+impl ChartProjection {
+	fn for_fov(fov_degrees: f32) -> ChartProjection {
+		if fov_degrees > MAX_GNOMONIC_FOV {
+			ChartProjection::Stereographic
+		} else {
+			ChartProjection::Gnomonic
+		}
+	}
+
+	fn name(&self) -> &'static str {
+		match self {
+			ChartProjection::Gnomonic => "gnomonic",
+			ChartProjection::Stereographic => "stereographic",
+		}
+	}
+
+	// Distance from the centre of the chart for an angle from its centre
+	fn extent(&self, angle: f32) -> f32 {
+		match self {
+			ChartProjection::Gnomonic => angle.tan(),
+			ChartProjection::Stereographic => 2.0 * (angle / 2.0).tan(),
+		}
+	}
+}
+
+// What the chart shows, as seen from inside the sky : the direction at its
+// centre, the one towards its top and the vertical field of view in degrees
+#[derive(Clone, Copy, Debug)]
+pub struct ChartView {
+	pub center: Vec3,
+	pub up: Vec3,
+	pub fov: f32,
+	pub aspect: f32,
+	pub projection: ChartProjection,
+}
+
+impl ChartView {
+	fn right(&self) -> Vec3 {
+		self.center.cross(self.up)
+	}
+
+	fn height(&self) -> f32 {
+		CHART_WIDTH / self.aspect
+	}
+
+	fn project(&self, direction: Vec3) -> Option<Vec2> {
+		let direction = direction.normalize();
+		let (x, y, z) = (direction.dot(self.right()), direction.dot(self.up), direction.dot(self.center));
+
+		let projected = match self.projection {
+			ChartProjection::Gnomonic if z > 0.01 => Vec2::new(x / z, y / z),
+			ChartProjection::Stereographic if z > -0.5 => Vec2::new(2.0 * x / (1.0 + z), 2.0 * y / (1.0 + z)),
+			_ => return None,
+		};
+
+		let scale = self.height() / 2.0 / self.projection.extent(self.fov.to_radians() / 2.0);
+		Some(Vec2::new(CHART_WIDTH / 2.0 + projected.x * scale, self.height() / 2.0 - projected.y * scale))
+	}
+
+	fn contains(&self, point: Vec2, margin: f32) -> bool {
+		point.x > -margin && point.x < CHART_WIDTH + margin && point.y > -margin && point.y < self.height() + margin
+	}
+}
+
+pub struct ChartStar {
+	pub position: Vec3,
+	pub magnitude: f32,
+	pub name: Option<String>,
+}
+
+pub struct ChartFigure {
+	pub name: String,
+	pub stars: Vec<Vec3>,
+	pub lines: Vec<[u32; 2]>,
+}
+
+pub fn escape(text: &str) -> String {
+	text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// Coordinates of a direction, right ascension in hours and declination in degrees
+pub fn celestial_coordinates(direction: Vec3) -> (f32, f32) {
+	let direction = direction.normalize();
+	let rah = direction.x.atan2(direction.z).to_degrees().rem_euclid(360.0) / 15.0;
+	(rah, direction.y.clamp(-1.0, 1.0).asin().to_degrees())
+}
+
+// Path through the projected points, broken where they leave the chart
+fn grid_path(view: &ChartView, points: impl Iterator<Item = Vec3>) -> String {
+	let mut path = String::new();
+	let mut drawing = false;
+
+	for point in points {
+		match view.project(point).filter(|point| view.contains(*point, CHART_WIDTH)) {
+			Some(point) => {
+				let _ = write!(path, "{}{:.1} {:.1} ", if drawing { "L" } else { "M" }, point.x, point.y);
+				drawing = true;
+			}
+			None => drawing = false,
+		}
+	}
+
+	path
+}
+
+// Black stars on white paper, the faintest ones first so bright ones are drawn on
+// top. Without labels, no star nor constellation is named
+pub fn chart_svg(view: &ChartView, stars: &[ChartStar], figures: &[ChartFigure], faintest: f32, labels: bool, title: &str) -> String {
+	let height = view.height();
+	let faintest = faintest.min(FAINTEST_PRINTED);
+	let mut svg = String::new();
+
+	let _ = writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h:.0}" viewBox="0 0 {w} {h:.0}" font-family="sans-serif">"#, w = CHART_WIDTH, h = height);
+	let _ = writeln!(svg, r#"<clipPath id="frame"><rect width="{}" height="{:.0}"/></clipPath>"#, CHART_WIDTH, height);
+	let _ = writeln!(svg, r#"<rect width="{}" height="{:.0}" fill="white"/>"#, CHART_WIDTH, height);
+	let _ = writeln!(svg, r#"<g clip-path="url(#frame)">"#);
+
+	// Finer grid when zoomed in, every declination line and right ascension hour otherwise
+	let (dec_step, ra_step) = if view.fov > 30.0 { (10, 1.0) } else { (5, 0.5) };
+	let _ = writeln!(svg, r##"<g fill="none" stroke="#bbb" stroke-width="0.6">"##);
+	for dec in (-90 + dec_step..90).step_by(dec_step as usize) {
+		let path = grid_path(view, (0..=360).map(|degree| celestial_to_cartesian(degree as f64 / 15.0, dec as f64)));
+		if !path.is_empty() {
+			let _ = writeln!(svg, r#"<path d="{}"/>"#, path);
+		}
+	}
+	for hour in 0..(24.0 / ra_step) as usize {
+		let rah = hour as f64 * ra_step;
+		let path = grid_path(view, (-89..=89).map(|dec| celestial_to_cartesian(rah, dec as f64)));
+		if !path.is_empty() {
+			let _ = writeln!(svg, r#"<path d="{}"/>"#, path);
+		}
+	}
+	let _ = writeln!(svg, "</g>");
+
+	let _ = writeln!(svg, r##"<g stroke="#777" stroke-width="1.2">"##);
+	for figure in figures {
+		for line in figure.lines.iter() {
+			let (Some(start), Some(end)) = (figure.stars.get(line[0] as usize), figure.stars.get(line[1] as usize)) else {
+				continue;
+			};
+			let (Some(start), Some(end)) = (view.project(*start), view.project(*end)) else {
+				continue;
+			};
+			if view.contains(start, CHART_WIDTH) && view.contains(end, CHART_WIDTH) {
+				let _ = writeln!(svg, r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}"/>"#, start.x, start.y, end.x, end.y);
+			}
+		}
+	}
+	let _ = writeln!(svg, "</g>");
+
+	let mut shown : Vec<(Vec2, &ChartStar)> = stars.iter()
+		.filter(|star| star.magnitude <= faintest)
+		.filter_map(|star| view.project(star.position).map(|point| (point, star)))
+		.filter(|(point, _)| view.contains(*point, 10.0))
+		.collect();
+	shown.sort_by(|a, b| b.1.magnitude.total_cmp(&a.1.magnitude));
+
+	let radius = |magnitude: f32| (0.8 + 1.1 * (faintest - magnitude)).clamp(0.8, 8.0);
+
+	let _ = writeln!(svg, r#"<g fill="black" stroke="white" stroke-width="0.8">"#);
+	for (point, star) in shown.iter() {
+		let _ = writeln!(svg, r#"<circle cx="{:.1}" cy="{:.1}" r="{:.1}"/>"#, point.x, point.y, radius(star.magnitude));
+	}
+	let _ = writeln!(svg, "</g>");
+
+	let _ = writeln!(svg, r##"<g font-size="11" fill="#333">"##);
+	for (point, star) in shown.iter().filter(|(_, star)| labels && star.magnitude <= NAMED_MAGNITUDE) {
+		if let Some(name) = &star.name {
+			let _ = writeln!(svg, r#"<text x="{:.1}" y="{:.1}">{}</text>"#, point.x + radius(star.magnitude) + 3.0, point.y + 4.0, escape(name));
+		}
+	}
+	let _ = writeln!(svg, "</g>");
+
+	let _ = writeln!(svg, r##"<g font-size="16" font-style="italic" fill="#246" text-anchor="middle">"##);
+	for figure in figures.iter().filter(|figure| labels && !figure.stars.is_empty()) {
+		let center = figure.stars.iter().copied().sum::<Vec3>();
+		if let Some(point) = view.project(center).filter(|point| view.contains(*point, 0.0)) {
+			let _ = writeln!(svg, r#"<text x="{:.1}" y="{:.1}">{}</text>"#, point.x, point.y, escape(&figure.name));
+		}
+	}
+	let _ = writeln!(svg, "</g>");
+	let _ = writeln!(svg, "</g>");
+
+	let (rah, dec) = celestial_coordinates(view.center);
+	let caption = format!(
+		"RA {:02}h{:02}m  Dec {:+.0}°  field {:.0}°  {}  stars to magnitude {:.1}",
+		rah as u32, ((rah.fract() * 60.0) as u32).min(59), dec, view.fov, view.projection.name(), faintest,
+	);
+	let _ = writeln!(svg, r#"<rect width="{}" height="{:.0}" fill="none" stroke="black" stroke-width="2"/>"#, CHART_WIDTH, height);
+	let _ = writeln!(svg, r#"<text x="12" y="26" font-size="18">{}</text>"#, escape(title));
+	let _ = writeln!(svg, r##"<text x="12" y="{:.0}" font-size="11" fill="#555">{}</text>"##, height - 10.0, escape(&caption));
+	let _ = writeln!(svg, "</svg>");
+
+	svg
+}
+
+// The loaded catalog, so that charts show the same stars as the sky
+fn chart_stars() -> Vec<ChartStar> {
+	let catalog = star_catalog::load_catalog();
+	let names = star_catalog::star_names(&catalog);
+	catalog.into_iter().zip(names)
+		.map(|(star, name)| ChartStar {
+			position: celestial_to_cartesian(star.rah as f64, star.dec as f64),
+			magnitude: star.magnitude,
+			name,
+		})
+		.collect()
+}
+
+fn chart_figure(constellation: &Constellation, name: String) -> ChartFigure {
+	ChartFigure {
+		name,
+		stars: constellation.stars.iter().map(|star| celestial_to_cartesian(star.rah, star.dec)).collect(),
+		lines: constellation.lines.clone(),
+	}
+}
+
+// A page centred on one of the western constellations, north up, with its
+// neighbours' figures around it. None for an unknown name
+pub fn constellation_chart(name: &str, projection: Option<ChartProjection>) -> Option<String> {
+	let constellations : Vec<Constellation> = serde_json::from_str(include_str!("../data/constellations.json")).ok()?;
+	let target = constellations.iter().find(|constellation| constellation.name.eq_ignore_ascii_case(name))?;
+
+	let figure = chart_figure(target, target.name.clone());
+	let center = figure.stars.iter().copied().sum::<Vec3>().try_normalize()?;
+	let widest = figure.stars.iter()
+		.map(|star| star.normalize().dot(center).clamp(-1.0, 1.0).acos().to_degrees())
+		.fold(0.0, f32::max);
+	let fov = (2.0 * widest * CONSTELLATION_MARGIN).max(MIN_CONSTELLATION_FOV);
+
+	// North up, unless the constellation sits on a pole
+	let up = (Vec3::Y - center * center.y).try_normalize().unwrap_or(Vec3::Z);
+
+	let view = ChartView {
+		center,
+		up,
+		fov,
+		aspect: PAGE_ASPECT,
+		projection: projection.unwrap_or(ChartProjection::for_fov(fov * PAGE_ASPECT)),
+	};
+
+	let figures : Vec<ChartFigure> = constellations.iter().map(|constellation| chart_figure(constellation, constellation.name.clone())).collect();
+	Some(chart_svg(&view, &chart_stars(), &figures, FAINTEST_PRINTED, true, &target.name))
+}
+
+// Writes what the camera shows, with the figures of the current sky culture
+pub fn export_view(
+	actions: ActionInput,
+	settings: Res<Settings>,
+	locale: Res<Locale>,
+	sky: Res<Sky>,
+	camera_query: Query<(&Camera, &GlobalTransform, &Projection), With<Player>>,
+	mut stars: Local<Vec<ChartStar>>,
+) {
+	if !actions.just_pressed(Action::Chart) {
+		return;
+	}
+
+	let Ok((camera, camera_transform, projection)) = camera_query.get_single() else {
+		return;
+	};
+
+	let fov = match projection {
+		Projection::Perspective(perspective) => perspective.fov.to_degrees(),
+		_ => 60.0,
+	};
+	let aspect = camera.logical_viewport_size().map(|size| size.x / size.y).unwrap_or(PAGE_ASPECT);
+
+	let view = ChartView {
+		center: *camera_transform.forward(),
+		up: *camera_transform.up(),
+		fov,
+		aspect,
+		projection: ChartProjection::for_fov(fov * aspect),
+	};
+
+	if stars.is_empty() {
+		*stars = chart_stars();
+	}
+
+	let figures : Vec<ChartFigure> = sky.content.iter()
+		.map(|constellation| chart_figure(constellation, locale.constellation(&constellation.name)))
+		.collect();
+
+	// Names follow the sky labels setting, so that charts can be handed out as quizzes
+	let svg = chart_svg(&view, &stars, &figures, settings.limiting_magnitude, settings.labels, "Astraea");
+	crate::settings::export(&format!("astraea-chart-{}.svg", unix_time() as u64), svg.as_bytes());
+}
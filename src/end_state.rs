@@ -3,12 +3,37 @@ use bevy::prelude::*;
 use crate::GameState;
 use crate::GameOver;
 use crate::GameData;
+use crate::Localized;
+use crate::ActionInput;
+use crate::InputMap;
+use crate::MenuItem;
+use crate::SoundEffect;
+use crate::GameRng;
+use crate::SkyCultures;
+use crate::Sky;
+use crate::Locale;
+
+use crate::result_card::ResultCard;
+use crate::result_card::card_svg;
+
+use crate::seed::RunMode;
+use crate::seed::date;
+use crate::seed::unix_time;
+
+use crate::input::Action;
 
 pub fn setup(
 	mut commands: Commands,
-	_asset_server: Res<AssetServer>,
 	game_data: Res<GameData>,
+	input_map: Res<InputMap>,
+	mut sound_effects: EventWriter<SoundEffect>,
+	game_rng: Res<GameRng>,
+	cultures: Res<SkyCultures>,
+	sky: Res<Sky>,
+	locale: Res<Locale>,
 ) {
+	sound_effects.send(SoundEffect::GameOver);
+
     let container_node = NodeBundle {
         style: Style {
             width: Val::Percent(100.0), 
@@ -37,27 +62,91 @@ pub fn setup(
         ..default()
     };
 
+    let continue_text_style = TextStyle {
+        font_size: 20.0, 
+        color: Color::srgb(0.4,0.4,0.4),
+        // font: asset_server.load("fonts/FiraSans-Regular.ttf"), 
+        ..default()
+    };
+
     let top_text_node = TextBundle::from_section(
-        "Game Over", 
+        "", 
         top_text_style,
     );
 
     let bottom_text_node = TextBundle::from_section(
-        format!("final score : {}", game_data.score), 
+        "", 
         bottom_text_style,
     );
 
-    let top_text = commands.spawn((top_text_node, GameOver)).id();
-    let bottom_text = commands.spawn((bottom_text_node, GameOver)).id();
+    let continue_text_node = TextBundle::from_section(
+        "", 
+        continue_text_style.clone(),
+    ).with_style(Style {
+        margin: UiRect::top(Val::Px(20.0)),
+        ..default()
+    });
+
+    let top_text = commands.spawn((top_text_node, GameOver, Localized::new("end-title"))).id();
+    let bottom_text = commands.spawn((
+    	bottom_text_node,
+    	GameOver,
+    	Localized::new("end-score").with_arg("score", game_data.score.to_string()),
+    )).id();
+
+    let continue_text = commands.spawn((
+    	continue_text_node,
+    	GameOver,
+    	Localized::new("end-continue").with_arg("key", input_map.key_name(Action::Next)),
+    	MenuItem { action: Action::Next, order: 0 },
+    	Interaction::default(),
+    )).id();
+
+    // The code replays this run, the daily challenge is named by its date
+    let code_localized = match game_rng.mode {
+    	RunMode::Daily(day) => {
+    		let (year, month, day_of_month) = date(day);
+    		Localized::new("end-daily").with_arg("date", format!("{}-{:02}-{:02}", year, month, day_of_month))
+    	}
+    	_ => Localized::new("end-code"),
+    };
+
+    let code_localized = code_localized.with_arg("code", game_rng.code(cultures.selected));
+
+    // Score, run and date over the map of the constellations asked
+    let (year, month, day_of_month) = date((unix_time() / 86400.0).floor() as i64);
+    let found = game_data.answers().iter().filter(|(_, right)| *right).count();
+    let card_texts = [
+    	"Astraea".to_string(),
+    	locale.text_with("end-score", &[("score".into(), game_data.score.to_string())]),
+    	code_localized.text(&locale),
+    	locale.text_with("result-found", &[("found".into(), found.to_string()), ("asked".into(), game_data.answers().len().to_string())]),
+    	format!("{}, {}-{:02}-{:02}", locale.culture(&cultures.current().name), year, month, day_of_month),
+    ];
+    commands.insert_resource(ResultCard {
+    	svg: card_svg(&card_texts, &sky.content, game_data.answers()),
+    });
+
+    let code_text = commands.spawn((
+    	TextBundle::from_section("", continue_text_style.clone()),
+    	GameOver,
+    	code_localized,
+    )).id();
+
+    let card_text = commands.spawn((
+    	TextBundle::from_section("", continue_text_style.clone()),
+    	GameOver,
+    	Localized::new("end-card").with_arg("key", input_map.key_name(Action::Chart)),
+    )).id();
 
-    commands.entity(container).push_children(&[top_text, bottom_text]);
+    commands.entity(container).push_children(&[top_text, bottom_text, code_text, card_text, continue_text]);
 }
 
 pub fn player_interact(
-	keys: Res<ButtonInput<KeyCode>>,
+	actions: ActionInput,
 	mut game_state: ResMut<NextState<GameState>>
 ) {
-	if keys.just_pressed(KeyCode::Space) {
+	if actions.just_pressed(Action::Next) {
 		info!("start space");
 		game_state.set(GameState::Start);
 	}
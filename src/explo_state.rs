@@ -2,31 +2,42 @@ use bevy::prelude::*;
 use bevy::input::mouse::MouseScrollUnit;
 use bevy::input::mouse::MouseWheel;
 
-use std::f32::consts::{E, PI};
+use std::f32::consts::E;
 
 use crate::Player;
 use crate::GameState;
 use crate::ConstellationModel;
 use crate::Sky;
 use crate::MainGame;
+use crate::Locale;
+use crate::InfoCard;
+use crate::ActionInput;
+use crate::Settings;
+
+use crate::input::Action;
+
+use crate::info_card::spawn_card;
 
 use crate::spawn_cons_lines;
 
 use crate::CONS_VIEW_RADIUS;
 use crate::MOVE_SPEED;
 use crate::ROT_SPEED;
+use crate::ZOOM_STEP;
+use crate::SCROLL_PIXELS_PER_LINE;
 
 #[derive(Component)]
 pub struct InfoLabel;
 
 pub fn setup (
 	sky : Res<Sky>,
+	settings: Res<Settings>,
 	mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
 	for constellation in sky.content.iter() {
-		spawn_cons_lines(&mut commands, &mut meshes, &mut materials, constellation.clone());
+		spawn_cons_lines(&mut commands, &mut meshes, &mut materials, constellation.clone(), settings.line_brightness);
 	}
 	
 	let centered_container_node = NodeBundle {
@@ -55,10 +66,13 @@ pub fn setup (
     let info_label = commands.spawn((info_label_node, MainGame, InfoLabel)).id();
 
     commands.entity(centered_container).push_children(&[info_label]);
+
+    spawn_card(&mut commands);
 }
 
 pub fn player_mouse_move (
     buttons: Res<ButtonInput<MouseButton>>,
+    settings: Res<Settings>,
     mut player_query: Query<(&mut Player, &Camera, &mut GlobalTransform)>,
     window_query: Query<&Window, With<bevy::window::PrimaryWindow>>,
     ui_query: Query<&Interaction, With<Button>>,
@@ -103,7 +117,7 @@ pub fn player_mouse_move (
 	    return;
 	};
 
-	let delta_rotation = rotate_to_align(new_ray, old_ray); 
+	let delta_rotation = rotate_to_align(new_ray, old_ray, MOVE_SPEED * settings.drag_sensitivity); 
 
 	player.target_rotation = Some(delta_rotation * local_transform.rotation );
 	player.l_drag_pos = Some(new_cursor);
@@ -111,6 +125,7 @@ pub fn player_mouse_move (
 
 pub fn player_mouse_rotate (
     buttons: Res<ButtonInput<MouseButton>>,
+    settings: Res<Settings>,
     mut player_query: Query<(&mut Player, &mut GlobalTransform)>,
     window_query: Query<&Window, With<bevy::window::PrimaryWindow>>,
     ui_query: Query<&Interaction, With<Button>>,
@@ -157,7 +172,7 @@ pub fn player_mouse_rotate (
    	    return;
    	}
 	
-    let angle = (old_vec.dot(new_vec) / (old_vec.length() * new_vec.length())).acos() * ROT_SPEED;
+    let angle = (old_vec.dot(new_vec) / (old_vec.length() * new_vec.length())).acos() * ROT_SPEED * settings.drag_sensitivity;
 
     let signed_angle = if old_vec.perp_dot(new_vec) < 0.0 {
     	angle
@@ -171,34 +186,164 @@ pub fn player_mouse_rotate (
 	player.r_drag_pos = Some(new_cursor);
 }
 
+pub fn player_touch(
+    touches: Res<Touches>,
+    settings: Res<Settings>,
+    mut player_query: Query<(&mut Player, &Camera, &GlobalTransform, &Projection)>,
+    ui_query: Query<&Interaction, With<Button>>,
+) {
+    for interaction in ui_query.iter() {
+        if *interaction == Interaction::Pressed {
+        	// Button tapped
+            return;
+        }
+    }
+
+	let Ok((mut player, camera, global_transform, projection)) = player_query.get_single_mut() else {
+	    return;
+	};
+	let local_transform = &global_transform.compute_transform();
+
+	let active_touches : Vec<&bevy::input::touch::Touch> = touches.iter().collect();
+
+	let touch_moving = active_touches.iter().any(|touch| touch.delta() != Vec2::ZERO);
+
+	if player.touch_moving != touch_moving {
+		player.touch_moving = touch_moving;
+	}
+
+	if !touch_moving {
+		return;
+	}
+
+	match active_touches[..] {
+		// One finger drags the sky like the left mouse button
+		[touch] => {
+			let Some(old_ray) = camera.viewport_to_world(global_transform, touch.previous_position()) else {
+			    return;
+			};
+
+			let Some(new_ray) = camera.viewport_to_world(global_transform, touch.position()) else {
+			    return;
+			};
+
+			let delta_rotation = rotate_to_align(new_ray, old_ray, MOVE_SPEED * settings.drag_sensitivity);
+
+			player.target_rotation = Some(delta_rotation * local_transform.rotation);
+		}
+		// Two fingers pinch to zoom and twist to roll
+		[first_touch, second_touch] => {
+			let old_vec = first_touch.previous_position() - second_touch.previous_position();
+			let new_vec = first_touch.position() - second_touch.position();
+
+			if new_vec.length() < f32::EPSILON || old_vec.length() < f32::EPSILON {
+		   	    return;
+		   	}
+
+			if let Projection::Perspective(ref perspective) = *projection {
+				let middle = (first_touch.position() + second_touch.position()) / 2.0;
+				player.zoom(perspective.fov, old_vec.length() / new_vec.length(), Some(middle).filter(|_| settings.zoom_to_cursor));
+			}
+
+			let signed_angle = -old_vec.angle_between(new_vec) * ROT_SPEED * settings.drag_sensitivity;
+
+			let delta_rotation = Quat::from_axis_angle(local_transform.forward().into(), signed_angle);
+
+			player.target_rotation = Some(delta_rotation * local_transform.rotation);
+		}
+		_ => {}
+	}
+}
+
 pub fn zoom(
 	mut evr_scroll: EventReader<MouseWheel>,
-	mut projection_query: Query<&mut Projection, With<Player>>,
+	settings: Res<Settings>,
+	mut player_query: Query<(&mut Player, &Projection)>,
+	window_query: Query<&Window, With<bevy::window::PrimaryWindow>>,
 ) {
-	let Ok(mut projection) = projection_query.get_single_mut() else {
+	let Ok((mut player, projection)) = player_query.get_single_mut() else {
 		//info!("no camera projection");
 		return;
 	};
 
-	let Projection::Perspective(ref mut perspective) = *projection else {
+	let Projection::Perspective(ref perspective) = *projection else {
 		//info!("no camera perspective");
 		return;
 	};
+
+	let cursor_position = window_query.get_single().ok()
+		.and_then(|window| window.cursor_position())
+		.filter(|_| settings.zoom_to_cursor);
 	
 	for ev in evr_scroll.read() {
-        match ev.unit {
-            MouseScrollUnit::Line => {
-				perspective.fov = (0.6*PI).min((0.02*PI).max(perspective.fov * 0.9_f32.powf(ev.y)));
-				//info!("Scroll (line units): vertical: {}, horizontal: {}", ev.y, ev.x);
-            }
-            MouseScrollUnit::Pixel => {
-                //info!("Scroll (pixel units): vertical: {}, horizontal: {}", ev.y, ev.x);
-            }
-        }
+		// Wheels scroll by lines, trackpads and most browsers by pixels
+        let lines = match ev.unit {
+            MouseScrollUnit::Line => ev.y,
+            MouseScrollUnit::Pixel => ev.y / SCROLL_PIXELS_PER_LINE,
+        };
+
+        player.zoom(perspective.fov, ZOOM_STEP.powf(lines), cursor_position);
     }
 }
 
-fn rotate_to_align(ray_1: Ray3d, ray_2: Ray3d) -> Quat {
+pub fn zoom_camera(
+	mut player_query: Query<(&mut Player, &mut Transform, &mut Projection)>,
+	window_query: Query<&Window, With<bevy::window::PrimaryWindow>>,
+) {
+	let Ok((mut player, mut transform, mut projection)) = player_query.get_single_mut() else {
+        return;
+    };
+
+	let Some(target_fov) = player.target_fov else {
+        return;
+    };
+
+	let Projection::Perspective(ref mut perspective) = *projection else {
+		return;
+	};
+
+	let old_fov = perspective.fov;
+	let mut new_fov = old_fov + (target_fov - old_fov) * 0.1;
+
+	if (target_fov - new_fov).abs() < 0.001 {
+		new_fov = target_fov;
+		player.target_fov = None;
+	}
+
+	perspective.fov = new_fov;
+
+	let Some(anchor) = player.zoom_anchor else {
+		return;
+	};
+
+	if player.target_fov.is_none() {
+		player.zoom_anchor = None;
+	}
+
+	let Ok(window) = window_query.get_single() else {
+		return;
+	};
+
+	// Turn the camera so that what was under the anchor stays under it
+	let ndc = Vec2::new(2.0 * anchor.x / window.width() - 1.0, 1.0 - 2.0 * anchor.y / window.height());
+	let old_direction = view_direction(ndc, old_fov, perspective.aspect_ratio);
+	let new_direction = view_direction(ndc, new_fov, perspective.aspect_ratio);
+
+	let correction = Quat::from_rotation_arc(new_direction, old_direction);
+
+	transform.rotation *= correction;
+
+	if let Some(target_rotation) = player.target_rotation {
+		player.target_rotation = Some(target_rotation * correction);
+	}
+}
+
+fn view_direction(ndc: Vec2, fov: f32, aspect_ratio: f32) -> Vec3 {
+	let half_height = (fov / 2.0).tan();
+	Vec3::new(ndc.x * half_height * aspect_ratio, ndc.y * half_height, -1.0).normalize()
+}
+
+fn rotate_to_align(ray_1: Ray3d, ray_2: Ray3d, speed: f32) -> Quat {
     let pos_1 = ray_1.get_point(1.0);
     let pos_2 = ray_2.get_point(1.0);
     
@@ -212,7 +357,7 @@ fn rotate_to_align(ray_1: Ray3d, ray_2: Ray3d) -> Quat {
     }
 
     let dot_product = dir_1.dot(dir_2).clamp(-1.0, 1.0);
-    let angle_of_rotation = dot_product.acos() * MOVE_SPEED;
+    let angle_of_rotation = dot_product.acos() * speed;
 
     if angle_of_rotation.is_nan() || angle_of_rotation.is_infinite() {
         return Quat::IDENTITY;
@@ -242,11 +387,10 @@ pub fn rotate_camera(
 }
 
 pub fn player_interact(
-	keys: Res<ButtonInput<KeyCode>>,
+	actions: ActionInput,
 	mut game_state: ResMut<NextState<GameState>>,
-	//mut player_query: Query<(&mut Player, &mut Transform)>,
 ) {
-	if keys.just_pressed(KeyCode::Escape) {
+	if actions.just_pressed(Action::Back) {
 		game_state.set(GameState::Start);
 	}
 }
@@ -257,6 +401,9 @@ pub fn constellation_opacity(
     constellation_query: Query<(&Handle<StandardMaterial>, &ConstellationModel)>, // Query all constellation lines
     window_query: Query<&Window, With<bevy::window::PrimaryWindow>>,
     mut info_label_query: Query<&mut Text, With<InfoLabel>>,
+    sky: Res<Sky>,
+    locale: Res<Locale>,
+    mut info_card: ResMut<InfoCard>,
 ) {
 	let (_player, camera, global_transform) = player_query.single();
 	let window = window_query.single();
@@ -264,7 +411,7 @@ pub fn constellation_opacity(
 		return;
 	};
 
-	let Some(mouse_ray) = camera.viewport_to_world(&global_transform, cursor_position) else {
+	let Some(mouse_ray) = camera.viewport_to_world(global_transform, cursor_position) else {
 	    return;
 	};
 
@@ -291,9 +438,21 @@ pub fn constellation_opacity(
         }
     }
 
+    // Only show the card when really hovering the constellation
+    if closest_const_pos.distance(cursor_global_pos) < CONS_VIEW_RADIUS / 2.0 {
+    	info_card.show(&closest_const_name);
+    } else {
+    	info_card.hide();
+    }
+
     let Ok(mut info_label) = info_label_query.get_single_mut() else {
     	return;
     };
 	
-    info_label.sections[0].value = closest_const_name;
+    let display_name = locale.constellation(&closest_const_name);
+	
+    info_label.sections[0].value = match sky.get_constellation(&closest_const_name).description {
+    	Some(description) => format!("{}\n{}", display_name, locale.constellation_description(&closest_const_name, &description)),
+    	None => display_name,
+    };
 }
@@ -8,6 +8,18 @@ use crate::MainGame;
 use crate::Sky;
 use crate::Constellation;
 use crate::ConstellationModel;
+use crate::Locale;
+use crate::InfoCard;
+use crate::ActionInput;
+use crate::InputMap;
+use crate::Settings;
+use crate::SoundEffect;
+use crate::GameRng;
+
+use crate::input::Action;
+use crate::gamepad::pressed_answer;
+
+use crate::info_card::spawn_card;
 
 use crate::celestial_to_cartesian;
 use crate::spawn_cons_lines;
@@ -17,7 +29,16 @@ use crate::RIGHT_BUTTON;
 use crate::WRONG_BUTTON;
 
 #[derive(Component)]
-pub struct AnswerButton;
+pub struct AnswerButton {
+	index: usize,
+	cons_name: String,
+}
+
+impl AnswerButton {
+	pub fn cons_name(&self) -> &str {
+		&self.cons_name
+	}
+}
 
 #[derive(Component)]
 pub struct HealthLabel;
@@ -36,6 +57,8 @@ pub struct GameData {
 	state: PlayerState,
 	target_cons_name: Option<String>,
 	target_cons_focused: bool,
+	// Constellations asked so far, and whether they were found
+	answers: Vec<(String, bool)>,
 }
 
 impl Default for GameData {
@@ -47,10 +70,47 @@ impl Default for GameData {
    	    	state: PlayerState::Playing,
    	    	target_cons_name: None,
    	    	target_cons_focused: false,
+   	    	answers: vec![],
    	    }
     }
 }
 
+impl GameData {
+	pub fn health(&self) -> usize {
+		self.health
+	}
+
+	pub fn target(&self) -> Option<&str> {
+		self.target_cons_name.as_deref()
+	}
+
+	pub fn answers(&self) -> &[(String, bool)] {
+		&self.answers
+	}
+
+	// Whether the camera was brought back on the constellation asked since it last moved
+	pub fn focused(&self) -> bool {
+		self.target_cons_focused
+	}
+
+	pub fn hinted(&self) -> bool {
+		self.state == PlayerState::Hinted
+	}
+
+	// What the hint label invites the player to do, tapping it does just that
+	fn suggested_action(&self) -> Option<Action> {
+		if !self.target_cons_focused {
+			Some(Action::Recenter)
+		} else if self.state == PlayerState::Playing {
+			Some(Action::Hint)
+		} else if self.state == PlayerState::Answered {
+			Some(Action::Next)
+		} else {
+			None
+		}
+	}
+}
+
 #[derive(Default, PartialEq, Debug)]
 enum PlayerState {
 	#[default]
@@ -62,6 +122,7 @@ enum PlayerState {
 pub fn setup(
 	mut commands: Commands, 
 	mut game_data: ResMut<GameData>,
+	mut info_card: ResMut<InfoCard>,
 	sky: Res<Sky>,
 ) {
     let container_node = NodeBundle {
@@ -89,7 +150,7 @@ pub fn setup(
 
     let container = commands.spawn(container_node).id();
 
-    for _i in 1..=4 {
+    for index in 0..4 {
         let button_node = ButtonBundle {
             style: button_style.clone(),
             border_color: BorderColor(Color::BLACK),
@@ -108,7 +169,12 @@ pub fn setup(
         );
 
         let button = commands.spawn((button_node, MainGame)).id();
-        let button_text = commands.spawn((button_text_node, AnswerButton, MainGame)).id();
+        let answer = AnswerButton {
+        	index,
+        	cons_name: "".into(),
+        };
+
+        let button_text = commands.spawn((button_text_node, answer, MainGame)).id();
 
         commands.entity(button).push_children(&[button_text]);
         commands.entity(container).push_children(&[button]);
@@ -189,58 +255,77 @@ pub fn setup(
     commands.spawn((top_right_label_node, MainGame, ScoreLabel));
 
     let centered_container = commands.spawn(centered_container_node).id();
-    let hint_label = commands.spawn((hint_label_node, MainGame, HintLabel)).id();
+    let hint_label = commands.spawn((hint_label_node, MainGame, HintLabel, Interaction::default())).id();
 
     commands.entity(centered_container).push_children(&[hint_label]);
 
+    spawn_card(&mut commands);
+    info_card.hide();
+
 	*game_data = GameData::default();
 	game_data.content = sky.as_string();
 }
 
 
 pub fn player_interact(
-    keys: Res<ButtonInput<KeyCode>>,
+    actions: ActionInput,
     mut player_query: Query<&mut Player>, 
     mut game_data: ResMut<GameData>, 
     sky: Res<Sky>, 
-    text_query: Query<&mut Text, With<AnswerButton>>,
+    locale: Res<Locale>,
+    settings: Res<Settings>,
+    mut info_card: ResMut<InfoCard>,
+    mut sound_effects: EventWriter<SoundEffect>,
+    mut game_rng: ResMut<GameRng>,
+    text_query: Query<(&mut Text, &mut AnswerButton)>,
     button_query: Query<(&mut BackgroundColor, &mut BorderColor), With<Button>>, 
 	constellation_line_query : Query<(Entity, &ConstellationModel)>,
     mut game_state: ResMut<NextState<GameState>>,
+    hint_tap_query: Query<&Interaction, (Changed<Interaction>, With<HintLabel>)>,
 	mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+	// Grouped, bevy systems take at most 16 parameters
+    (mut meshes, mut materials): (ResMut<Assets<Mesh>>, ResMut<Assets<StandardMaterial>>),
 ) {
     let Ok(mut player) = player_query.get_single_mut() else {
 		return
     };
 
-    if player.l_drag_pos.is_some() || player.r_drag_pos.is_some() {
+    if player.l_drag_pos.is_some() || player.r_drag_pos.is_some() || player.stick_moving || player.touch_moving {
     	game_data.target_cons_focused = false;
     }
+
+    let tapped_action = if hint_tap_query.iter().any(|interaction| *interaction == Interaction::Pressed) {
+    	game_data.suggested_action()
+    } else {
+    	None
+    };
   
-    if keys.just_pressed(KeyCode::Space) || game_data.target_cons_name.is_none() {
-        choose_constellation(&mut player, sky, text_query, button_query, constellation_line_query, commands, game_state, game_data);
+    if actions.just_pressed(Action::Next) || tapped_action == Some(Action::Next) || game_data.target_cons_name.is_none() {
+        info_card.hide();
+        choose_constellation(&mut player, sky, &locale, &mut game_rng, text_query, button_query, constellation_line_query, commands, game_state, game_data);
 		return
     }
     
-    if keys.just_pressed(KeyCode::Escape) {
+    if actions.just_pressed(Action::Back) {
    		game_state.set(GameState::Start);
    	}
 	
-    if keys.pressed(KeyCode::KeyI) {
+    if actions.pressed(Action::Hint) || tapped_action == Some(Action::Hint) {
     	if game_data.state != PlayerState::Playing {
     		info!("Invalid state : {:?}", game_data.state);
     	}
     	let Some(target_cons) = game_data.target_cons_name.clone() else {
 			return;
   		};
+  		if game_data.state == PlayerState::Playing {
+  			sound_effects.send(SoundEffect::Hint);
+  		}
   		game_data.state = PlayerState::Hinted;
-		spawn_cons_lines(&mut commands, &mut meshes, &mut materials, sky.get_constellation(&target_cons));
+		spawn_cons_lines(&mut commands, &mut meshes, &mut materials, sky.get_constellation(&target_cons), settings.line_brightness);
 		return;
 	}
 
-    if keys.pressed(KeyCode::KeyW) {
+    if actions.pressed(Action::Recenter) || tapped_action == Some(Action::Recenter) {
 		game_data.target_cons_focused = true;
 		let Some(target_constellation_name) = game_data.target_cons_name.clone() else {
 			return;
@@ -256,9 +341,17 @@ pub fn player_interact(
 }
 
 pub fn ui_labels(
-    mut label_query: Query<(&mut Text, Option<&HealthLabel>, Option<&ScoreLabel>, Option<&HintLabel>)>,
-    game_data: Res<GameData>
+    mut label_query: Query<(&mut Text, Option<&HealthLabel>, Option<&ScoreLabel>, Option<&HintLabel>), Without<AnswerButton>>,
+    mut answer_query: Query<(&mut Text, &AnswerButton)>,
+    game_data: Res<GameData>,
+    locale: Res<Locale>,
+    input_map: Res<InputMap>,
 ) {
+	if locale.is_changed() {
+		for (mut text, answer) in answer_query.iter_mut() {
+			text.sections[0].value = locale.constellation(&answer.cons_name);
+		}
+	}
 
 	for (mut text, health_label, score_label, hint_label) in label_query.iter_mut() {
 		if health_label.is_some() {
@@ -266,15 +359,12 @@ pub fn ui_labels(
 		} else if score_label.is_some() {
 			text.sections[0].value = format!("{}", game_data.score);
 		} else if hint_label.is_some() {
-			if !game_data.target_cons_focused {
-				text.sections[0].value = "press z to re-center".into();
-			} else if game_data.state == PlayerState::Playing {
-				text.sections[0].value = "press i to get an hint".into();
-			} else if game_data.state == PlayerState::Answered {
-				text.sections[0].value = "press space to continue".into();
-			} else {
-				text.sections[0].value = "guess the constellation".into();
-			}
+			text.sections[0].value = match game_data.suggested_action() {
+				Some(Action::Recenter) => locale.text_with("hint-recenter", &[("key".into(), input_map.key_name(Action::Recenter))]),
+				Some(Action::Hint) => locale.text_with("hint-get", &[("key".into(), input_map.key_name(Action::Hint))]),
+				Some(Action::Next) => locale.text_with("hint-continue", &[("key".into(), input_map.key_name(Action::Next))]),
+				_ => locale.text("hint-guess"),
+			};
 		}
 	}
 }
@@ -290,12 +380,17 @@ pub fn ui_buttons(
         ),
         With<Button>,
     >,
-    mut text_query: Query<&mut Text, With<AnswerButton>>,
+    answer_query: Query<&AnswerButton>,
     mut game_data: ResMut<GameData>,
 	mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
-    sky: Res<Sky>
+    mut info_card: ResMut<InfoCard>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    sky: Res<Sky>,
+    settings: Res<Settings>,
+    mut sound_effects: EventWriter<SoundEffect>,
 ) {	
 	if game_data.state == PlayerState::Answered {
 		return;
@@ -310,12 +405,18 @@ pub fn ui_buttons(
 	    children
 	) in &mut interaction_query {
 	    if *interaction == Interaction::Pressed {
-	    	if let Ok(text) = text_query.get_mut(children[0]) {
-	            pressed_button = Some(text.sections[0].value.clone());
+	    	if let Ok(answer) = answer_query.get(children[0]) {
+	            pressed_button = Some(answer.cons_name.clone());
 	        }
 	    }
 	}
 
+	if let Some(index) = pressed_answer(&gamepads, &gamepad_buttons) {
+		if let Some(answer) = answer_query.iter().find(|answer| answer.index == index) {
+			pressed_button = Some(answer.cons_name.clone());
+		}
+	}
+
 	let Some(selected_cons) = pressed_button else {
 		return;
 	};
@@ -325,7 +426,7 @@ pub fn ui_buttons(
 	};
 	
    	if game_data.state == PlayerState::Playing {
-   		spawn_cons_lines(&mut commands, &mut meshes, &mut materials, sky.get_constellation(&target_cons));
+   		spawn_cons_lines(&mut commands, &mut meshes, &mut materials, sky.get_constellation(&target_cons), settings.line_brightness);
    	}
 	    	
    	if target_cons == selected_cons {
@@ -334,13 +435,17 @@ pub fn ui_buttons(
    		} else {
    			game_data.score += 100;
    		}
+   		sound_effects.send(SoundEffect::Right);
    	} else {
    		game_data.health -= 1;
+   		sound_effects.send(SoundEffect::Wrong);
    	}
 
+   	game_data.answers.push((target_cons.clone(), target_cons == selected_cons));
    	game_data.content.retain(|x| x != &target_cons);
 
    	game_data.state = PlayerState::Answered;
+   	info_card.show(&target_cons);
 
  	for (
         _interaction,
@@ -348,16 +453,14 @@ pub fn ui_buttons(
         mut border_color,
         children
     ) in &mut interaction_query {
-    	if let Ok(text) = text_query.get_mut(children[0]) {
-    		let button_text = text.sections[0].value.clone();
-    		
-	        *color = if button_text == target_cons {
+    	if let Ok(answer) = answer_query.get(children[0]) {
+	        *color = if answer.cons_name == target_cons {
 	        	RIGHT_BUTTON.into()
 	        } else {
 	        	WRONG_BUTTON.into()
 	        };
 
-	        border_color.0 = if button_text == selected_cons {
+	        border_color.0 = if answer.cons_name == selected_cons {
 	        	Color::WHITE
 	        } else {
 	        	Color::BLACK
@@ -369,27 +472,37 @@ pub fn ui_buttons(
 fn choose_constellation(
 	player: &mut Player, 
 	sky: Res<Sky>, 
-    mut text_query: Query<&mut Text, With<AnswerButton>>,
+	locale: &Locale,
+	game_rng: &mut GameRng,
+    mut text_query: Query<(&mut Text, &mut AnswerButton)>,
     mut button_query: Query<(&mut BackgroundColor, &mut BorderColor), With<Button>>, 
 	constellation_line_query : Query<(Entity, &ConstellationModel)>,
 	mut commands: Commands,
     mut game_state: ResMut<NextState<GameState>>,
     mut game_data: ResMut<GameData>,
 ) {
-	if game_data.health == 0 {
-		game_state.set(GameState::End);
-	}
-
-	if game_data.content.len() < 4 {
+	if game_data.health == 0 || game_data.content.is_empty() {
 		game_state.set(GameState::End);
+		return;
 	}
 	
-    let mut rng = rand::thread_rng();
+    let rng = game_rng.rng();
     let mut cons_names = game_data.content.clone();
-    cons_names.shuffle(&mut rng);
-    let selected_cons_names = &cons_names[0..4];
+    cons_names.shuffle(rng);
+    let remaining = cons_names.len();
+
+    // Small sky cultures run out of figures not asked yet, the other answers are then taken among the ones asked
+    if remaining < 4 {
+    	let mut asked : Vec<String> = sky.as_string().into_iter().filter(|name| !cons_names.contains(name)).collect();
+    	asked.shuffle(rng);
+    	cons_names.extend(asked);
+    }
 
     let target_index = rng.next_u32().rem_euclid(4) as usize;
+    if target_index >= remaining {
+    	cons_names.swap(0, target_index);
+    }
+    let selected_cons_names = &cons_names[0..4];
     let target_constellation = sky.get_constellation(&selected_cons_names[target_index]);
 
     player.target_rotation = Some(constellation_center(target_constellation.clone()));
@@ -397,8 +510,9 @@ fn choose_constellation(
 
     info!("Target constellation: {}", target_constellation.name);
 
-    for (i, mut text) in text_query.iter_mut().enumerate() {
-        text.sections[0].value = selected_cons_names[i].clone();
+    for (mut text, mut answer) in text_query.iter_mut() {
+        answer.cons_name = selected_cons_names[answer.index].clone();
+        text.sections[0].value = locale.constellation(&answer.cons_name);
     }
 
     for (mut bg_color, mut border_color) in &mut button_query {
@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+
+use crate::Player;
+
+use crate::STICK_SPEED;
+use crate::STICK_ROLL_SPEED;
+use crate::TRIGGER_ZOOM_SPEED;
+
+// Face buttons in the order of the answer buttons, A B X Y on a xbox pad
+const ANSWER_BUTTONS: [GamepadButtonType; 4] = [
+	GamepadButtonType::South,
+	GamepadButtonType::East,
+	GamepadButtonType::West,
+	GamepadButtonType::North,
+];
+
+pub fn camera_sticks(
+	time: Res<Time>,
+	gamepads: Res<Gamepads>,
+	axes: Res<Axis<GamepadAxis>>,
+	button_axes: Res<Axis<GamepadButton>>,
+	mut player_query: Query<(&mut Player, &Transform, &Projection)>,
+) {
+	let Ok((mut player, transform, projection)) = player_query.get_single_mut() else {
+		return;
+	};
+
+	let Projection::Perspective(ref perspective) = *projection else {
+		return;
+	};
+
+	let mut pan = Vec2::ZERO;
+	let mut roll = 0.0;
+	let mut zoom = 0.0;
+
+	for gamepad in gamepads.iter() {
+		let axis = |axis_type: GamepadAxisType| axes.get(GamepadAxis::new(gamepad, axis_type)).unwrap_or(0.0);
+		let trigger = |button_type: GamepadButtonType| button_axes.get(GamepadButton::new(gamepad, button_type)).unwrap_or(0.0);
+
+		pan += Vec2::new(axis(GamepadAxisType::LeftStickX), axis(GamepadAxisType::LeftStickY));
+		roll += axis(GamepadAxisType::RightStickX);
+		zoom += trigger(GamepadButtonType::RightTrigger2) - trigger(GamepadButtonType::LeftTrigger2);
+	}
+
+	let delta_time = time.delta_seconds();
+
+	if zoom != 0.0 {
+		player.zoom(perspective.fov, 1.0 - zoom * TRIGGER_ZOOM_SPEED * delta_time, None);
+	}
+
+	let stick_moving = pan != Vec2::ZERO || roll != 0.0;
+
+	if player.stick_moving != stick_moving {
+		player.stick_moving = stick_moving;
+	}
+
+	if !stick_moving {
+		return;
+	}
+
+	// Panning slows down when zoomed in, like dragging the sky does
+	let pan_angle = STICK_SPEED * perspective.fov * delta_time;
+
+	let delta_rotation = Quat::from_axis_angle(transform.up().into(), -pan.x * pan_angle)
+		* Quat::from_axis_angle(transform.right().into(), pan.y * pan_angle)
+		* Quat::from_axis_angle(transform.forward().into(), roll * STICK_ROLL_SPEED * delta_time);
+
+	player.target_rotation = Some(delta_rotation * player.target_rotation.unwrap_or(transform.rotation));
+}
+
+pub fn pressed_answer(gamepads: &Gamepads, buttons: &ButtonInput<GamepadButton>) -> Option<usize> {
+	for gamepad in gamepads.iter() {
+		for (index, button_type) in ANSWER_BUTTONS.iter().enumerate() {
+			if buttons.just_pressed(GamepadButton::new(gamepad, *button_type)) {
+				return Some(index);
+			}
+		}
+	}
+
+	None
+}
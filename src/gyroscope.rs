@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::Player;
+use crate::ActionInput;
+
+use crate::input::Action;
+use crate::observer::Observer;
+
+// One DeviceOrientation reading, angles in degrees as the browser gives them,
+// screen being the rotation of the screen from portrait
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+struct OrientationSample {
+	time: f64,
+	alpha: f64,
+	beta: f64,
+	gamma: f64,
+	#[serde(default)]
+	screen: f64,
+}
+
+// Orientation stream recorded on a phone, replayed on desktop. Times of the
+// samples are in seconds from the start, itself a unix time
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct OrientationRecording {
+	latitude: f64,
+	longitude: f64,
+	start: f64,
+	samples: Vec<OrientationSample>,
+}
+
+impl OrientationRecording {
+	// Sample at some time since the replay started, looping at the end
+	fn sample_at(&self, elapsed: f64) -> Option<OrientationSample> {
+		let duration = self.samples.last()?.time;
+		let time = if duration > 0.0 {
+			elapsed.rem_euclid(duration)
+		} else {
+			0.0
+		};
+
+		let index = self.samples.partition_point(|sample| sample.time <= time).max(1);
+		Some(self.samples[index - 1])
+	}
+}
+
+#[derive(Resource)]
+pub struct Gyroscope {
+	pub enabled: bool,
+	recording: Option<OrientationRecording>,
+	started: f64,
+}
+
+impl Default for Gyroscope {
+	fn default() -> Self {
+		Gyroscope {
+			enabled: false,
+			recording: load_recording(),
+			started: 0.0,
+		}
+	}
+}
+
+impl Gyroscope {
+	// Latest orientation with the place and unix time it applies to
+	fn reading(&self, observer: &Observer, elapsed: f64) -> Option<(OrientationSample, Observer, f64)> {
+		if let Some(recording) = &self.recording {
+			let sample = recording.sample_at(elapsed - self.started)?;
+			let recorded_observer = Observer {
+				latitude: recording.latitude,
+				longitude: recording.longitude,
+				time: None,
+			};
+
+			return Some((sample, recorded_observer, recording.start + sample.time));
+		}
+
+		live_reading(observer)
+	}
+}
+
+pub fn toggle(
+	actions: ActionInput,
+	time: Res<Time>,
+	mut gyroscope: ResMut<Gyroscope>,
+) {
+	if !actions.just_pressed(Action::Gyroscope) {
+		return;
+	}
+
+	if gyroscope.enabled {
+		gyroscope.enabled = false;
+		info!("gyroscope off");
+		return;
+	}
+
+	if !start_sensor(&gyroscope) {
+		warn!("no orientation sensor, start with --orientation-replay <file> to replay a recording");
+		return;
+	}
+
+	gyroscope.enabled = true;
+	gyroscope.started = time.elapsed_seconds_f64();
+	info!("gyroscope on");
+}
+
+pub fn point_camera(
+	time: Res<Time>,
+	gyroscope: Res<Gyroscope>,
+	observer: Res<Observer>,
+	mut player_query: Query<&mut Player>,
+) {
+	if !gyroscope.enabled {
+		return;
+	}
+
+	let Some((sample, observer, unix_time)) = gyroscope.reading(&observer, time.elapsed_seconds_f64()) else {
+		return;
+	};
+
+	let Ok(mut player) = player_query.get_single_mut() else {
+		return;
+	};
+
+	player.target_rotation = Some(observer.horizon_to_sky(unix_time) * device_rotation(&sample));
+}
+
+// W3C convention : the device frame is turned by alpha around up, beta around
+// east then gamma around north. The camera looks out of the back of the phone,
+// which is its -z like the device's
+fn device_rotation(sample: &OrientationSample) -> Quat {
+	Quat::from_rotation_z(sample.alpha.to_radians() as f32)
+		* Quat::from_rotation_x(sample.beta.to_radians() as f32)
+		* Quat::from_rotation_y(sample.gamma.to_radians() as f32)
+		* Quat::from_rotation_z(-sample.screen.to_radians() as f32)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn load_recording() -> Option<OrientationRecording> {
+	let mut args = std::env::args().skip_while(|arg| arg != "--orientation-replay").skip(1);
+	let path = args.next().or_else(|| std::env::var("ASTRAEA_ORIENTATION_REPLAY").ok())?;
+
+	let content = match std::fs::read_to_string(&path) {
+		Ok(content) => content,
+		Err(error) => {
+			warn!("could not read orientation recording {} : {}", path, error);
+			return None;
+		}
+	};
+
+	match serde_json::from_str(&content) {
+		Ok(recording) => Some(recording),
+		Err(error) => {
+			warn!("invalid orientation recording {} : {}", path, error);
+			None
+		}
+	}
+}
+
+#[cfg(target_arch = "wasm32")]
+fn load_recording() -> Option<OrientationRecording> {
+	None
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn start_sensor(gyroscope: &Gyroscope) -> bool {
+	gyroscope.recording.is_some()
+}
+
+// The sensor may still be refused, the camera then just does not move
+#[cfg(target_arch = "wasm32")]
+fn start_sensor(_gyroscope: &Gyroscope) -> bool {
+	crate::web::orientation_start();
+	true
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn live_reading(_observer: &Observer) -> Option<(OrientationSample, Observer, f64)> {
+	None
+}
+
+#[cfg(target_arch = "wasm32")]
+fn live_reading(observer: &Observer) -> Option<(OrientationSample, Observer, f64)> {
+	let orientation = crate::web::orientation_latest()?;
+	let unix_time = crate::web::now_ms() / 1000.0;
+
+	let sample = OrientationSample {
+		time: 0.0,
+		alpha: orientation[0],
+		beta: orientation[1],
+		gamma: orientation[2],
+		screen: orientation[3],
+	};
+
+	// The browser position wins over the saved one once it is known
+	let observer = match crate::web::position_latest() {
+		Some(position) => Observer {
+			latitude: position[0],
+			longitude: position[1],
+			time: None,
+		},
+		None => *observer,
+	};
+
+	Some((sample, observer, unix_time))
+}
@@ -0,0 +1,175 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::Sky;
+use crate::MainGame;
+use crate::Locale;
+
+use crate::locale::Texts;
+use crate::locale::localized;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct InfoData {
+	// Keyed by language, English when missing
+	mythology: Texts,
+	best_months: Vec<u32>,
+	notable_stars: Vec<String>,
+	deep_sky: Vec<String>,
+}
+
+#[derive(Resource)]
+pub struct ConstellationInfo {
+	content: HashMap<String, InfoData>,
+}
+
+impl Default for ConstellationInfo {
+	fn default() -> Self {
+		ConstellationInfo {
+			content: serde_json::from_str(include_str!("../data/constellation_info.json")).expect("invalid constellation info json"),
+		}
+	}
+}
+
+// Name of the constellation whose card is shown, if any
+#[derive(Resource, Default)]
+pub struct InfoCard {
+	pub cons_name: Option<String>,
+}
+
+impl InfoCard {
+	pub fn show(&mut self, cons_name: &str) {
+		if self.cons_name.as_deref() != Some(cons_name) {
+			self.cons_name = Some(cons_name.into());
+		}
+	}
+
+	pub fn hide(&mut self) {
+		if self.cons_name.is_some() {
+			self.cons_name = None;
+		}
+	}
+}
+
+#[derive(Component)]
+pub struct InfoCardPanel;
+
+#[derive(Component)]
+pub struct InfoCardText;
+
+pub fn spawn_card(commands: &mut Commands) {
+	let card_node = NodeBundle {
+		style: Style {
+			position_type: PositionType::Absolute,
+			right: Val::Px(10.0),
+			top: Val::Px(60.0),
+			width: Val::Px(300.0),
+			padding: UiRect::all(Val::Px(10.0)),
+			..default()
+		},
+		background_color: Color::srgba(0.1, 0.1, 0.1, 0.8).into(),
+		visibility: Visibility::Hidden,
+		..default()
+	};
+
+	let card_text_node = TextBundle::from_sections([
+		TextSection::new(
+			"",
+			TextStyle {
+				// font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+				font_size: 24.0,
+				color: Color::WHITE,
+				..default()
+			},
+		),
+		TextSection::new(
+			"",
+			TextStyle {
+				// font: asset_server.load("fonts/FiraSans-Regular.ttf"),
+				font_size: 15.0,
+				color: Color::srgb(0.8, 0.8, 0.8),
+				..default()
+			},
+		),
+	]);
+
+	let card = commands.spawn((card_node, MainGame, InfoCardPanel)).id();
+	let card_text = commands.spawn((card_text_node, MainGame, InfoCardText)).id();
+
+	commands.entity(card).push_children(&[card_text]);
+}
+
+pub fn update_card(
+	info_card: Res<InfoCard>,
+	info: Res<ConstellationInfo>,
+	sky: Res<Sky>,
+	locale: Res<Locale>,
+	mut panel_query: Query<&mut Visibility, With<InfoCardPanel>>,
+	mut text_query: Query<&mut Text, With<InfoCardText>>,
+	added_query: Query<(), Added<InfoCardText>>,
+) {
+	if !info_card.is_changed() && !locale.is_changed() && added_query.is_empty() {
+		return;
+	}
+
+	let Ok(mut visibility) = panel_query.get_single_mut() else {
+		return;
+	};
+
+	let Ok(mut text) = text_query.get_single_mut() else {
+		return;
+	};
+
+	let Some(cons_name) = info_card.cons_name.clone() else {
+		*visibility = Visibility::Hidden;
+		return;
+	};
+
+	*visibility = Visibility::Inherited;
+
+	let constellation = sky.get_constellation(&cons_name);
+	let mut body : Vec<String> = vec![];
+
+	match info.content.get(&cons_name) {
+		Some(data) => {
+			body.push(localized(&data.mythology, &locale).to_string());
+			body.push(format!("{} : {}", locale.text("card-best-months"), month_names(&locale, &data.best_months)));
+			if !data.notable_stars.is_empty() {
+				body.push(format!("{} : {}", locale.text("card-notable-stars"), data.notable_stars.join(", ")));
+			}
+			if !data.deep_sky.is_empty() {
+				body.push(format!("{} : {}", locale.text("card-deep-sky"), data.deep_sky.join(", ")));
+			}
+		}
+		None => {
+			// Figures without notes still get their description and a season
+			if let Some(description) = &constellation.description {
+				body.push(locale.constellation_description(&cons_name, description));
+			}
+			body.push(format!("{} : {}", locale.text("card-best-months"), month_names(&locale, &best_months(constellation.rah))));
+		}
+	}
+
+	text.sections[0].value = format!("{}\n", locale.constellation(&cons_name));
+	text.sections[1].value = body.join("\n\n");
+}
+
+fn month_names(locale: &Locale, months: &[u32]) -> String {
+	months.iter()
+		.map(|month| locale.text(&format!("month-{}", month)))
+		.collect::<Vec<String>>()
+		.join(", ")
+}
+
+// Sidereal time at midnight is about 0h on September 21st and gains 2h a
+// month, pick the two months where the figure is highest around 10pm
+fn best_months(rah: f64) -> Vec<u32> {
+	let month = 9.7 + rah / 2.0;
+	let first = if month.fract() < 0.5 {
+		month.floor() as u32 + 11
+	} else {
+		month.floor() as u32
+	};
+
+	vec![first % 12 + 1, (first + 1) % 12 + 1]
+}
@@ -0,0 +1,295 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy::input::keyboard::Key;
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::ButtonState;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::settings;
+use crate::menu::MenuFocus;
+
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum Action {
+	Next,
+	Back,
+	Explore,
+	Hint,
+	Recenter,
+	ChangeCulture,
+	ChangeLanguage,
+	KeyBindings,
+	Gyroscope,
+	Settings,
+	Mute,
+	DailyChallenge,
+	EnterSeed,
+	Labels,
+	Chart,
+	Screenshot,
+	Bookmark,
+	GoToBookmark,
+	Tour,
+	ChangeTour,
+	Measure,
+	Instrument,
+	MatchInstrument,
+}
+
+impl Action {
+	pub const ALL: [Action; 23] = [
+		Action::Next,
+		Action::Back,
+		Action::Explore,
+		Action::Hint,
+		Action::Recenter,
+		Action::ChangeCulture,
+		Action::ChangeLanguage,
+		Action::KeyBindings,
+		Action::Gyroscope,
+		Action::Settings,
+		Action::Mute,
+		Action::DailyChallenge,
+		Action::EnterSeed,
+		Action::Labels,
+		Action::Chart,
+		Action::Screenshot,
+		Action::Bookmark,
+		Action::GoToBookmark,
+		Action::Tour,
+		Action::ChangeTour,
+		Action::Measure,
+		Action::Instrument,
+		Action::MatchInstrument,
+	];
+
+	// Defaults avoid the letters that move between QWERTY and AZERTY
+	fn default_key(&self) -> KeyCode {
+		match self {
+			Action::Next => KeyCode::Space,
+			Action::Back => KeyCode::Escape,
+			Action::Explore => KeyCode::KeyE,
+			Action::Hint => KeyCode::KeyI,
+			Action::Recenter => KeyCode::KeyR,
+			Action::ChangeCulture => KeyCode::KeyC,
+			Action::ChangeLanguage => KeyCode::KeyL,
+			Action::KeyBindings => KeyCode::KeyK,
+			Action::Gyroscope => KeyCode::KeyG,
+			Action::Settings => KeyCode::KeyO,
+			Action::Mute => KeyCode::KeyS,
+			Action::DailyChallenge => KeyCode::KeyD,
+			Action::EnterSeed => KeyCode::KeyN,
+			Action::Labels => KeyCode::KeyT,
+			Action::Chart => KeyCode::KeyP,
+			Action::Screenshot => KeyCode::F2,
+			Action::Bookmark => KeyCode::KeyB,
+			Action::GoToBookmark => KeyCode::KeyV,
+			Action::Tour => KeyCode::KeyU,
+			Action::ChangeTour => KeyCode::KeyJ,
+			Action::Measure => KeyCode::KeyX,
+			Action::Instrument => KeyCode::KeyF,
+			Action::MatchInstrument => KeyCode::KeyH,
+		}
+	}
+
+	// Menu actions without a button are reached with the d-pad
+	pub fn gamepad_button(&self) -> Option<GamepadButtonType> {
+		match self {
+			Action::Next => Some(GamepadButtonType::Start),
+			Action::Back => Some(GamepadButtonType::Select),
+			Action::Hint => Some(GamepadButtonType::LeftTrigger),
+			Action::Recenter => Some(GamepadButtonType::RightTrigger),
+			Action::ChangeLanguage => Some(GamepadButtonType::LeftThumb),
+			_ => None,
+		}
+	}
+
+	pub fn locale_key(&self) -> String {
+		format!("action-{:?}", self).to_lowercase()
+	}
+}
+
+#[derive(Resource, Clone)]
+pub struct InputMap {
+	bindings: HashMap<Action, KeyCode>,
+	// Characters the keyboard layout gives the keys pressed so far, the key
+	// codes are physical places, the W place of QWERTY reads Z on AZERTY
+	labels: HashMap<KeyCode, String>,
+}
+
+impl Default for InputMap {
+	fn default() -> Self {
+		InputMap {
+			bindings: Action::ALL.iter().map(|action| (*action, action.default_key())).collect(),
+			labels: HashMap::new(),
+		}
+	}
+}
+
+impl InputMap {
+	pub fn load() -> Self {
+		let mut input_map = InputMap::default();
+
+		let labels = settings::load::<HashMap<String, String>>("key_labels").unwrap_or_default();
+		input_map.labels = labels.into_iter()
+			.filter_map(|(key_name, label)| Some((parse_key(&key_name)?, label)))
+			.collect();
+
+		let Some(saved) = settings::load::<HashMap<Action, String>>("bindings") else {
+			return input_map;
+		};
+
+		for (action, key_name) in saved {
+			match parse_key(&key_name) {
+				Some(key) => {
+					input_map.bindings.insert(action, key);
+				}
+				None => warn!("unknown key {} for {:?}", key_name, action),
+			}
+		}
+
+		input_map
+	}
+
+	pub fn save(&self) {
+		let saved : HashMap<Action, String> = self.bindings.iter()
+			.map(|(action, key)| (*action, format!("{:?}", key)))
+			.collect();
+
+		settings::save("bindings", &saved);
+
+		let labels : HashMap<String, String> = self.labels.iter()
+			.map(|(key, label)| (format!("{:?}", key), label.clone()))
+			.collect();
+
+		settings::save("key_labels", &labels);
+	}
+
+	pub fn key(&self, action: Action) -> KeyCode {
+		self.bindings.get(&action).copied().unwrap_or(action.default_key())
+	}
+
+	// What the player reads on the key once the layout is known
+	pub fn key_name(&self, action: Action) -> String {
+		let key = self.key(action);
+		self.labels.get(&key).cloned().unwrap_or_else(|| key_name(key))
+	}
+
+	// Only printable characters, named keys like Space keep their own name
+	fn learn_label(&mut self, key: KeyCode, logical_key: &Key) -> bool {
+		let Key::Character(character) = logical_key else {
+			return false;
+		};
+
+		let label = character.to_uppercase();
+		if !BINDABLE_KEYS.contains(&key) || label.trim().is_empty() || self.labels.get(&key) == Some(&label) {
+			return false;
+		}
+
+		self.labels.insert(key, label);
+		true
+	}
+
+	// Taking a key already in use swaps the two bindings
+	pub fn rebind(&mut self, action: Action, key: KeyCode) {
+		let previous_key = self.key(action);
+
+		for other_action in Action::ALL {
+			if other_action != action && self.key(other_action) == key {
+				self.bindings.insert(other_action, previous_key);
+			}
+		}
+
+		self.bindings.insert(action, key);
+	}
+}
+
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+	keys: Res<'w, ButtonInput<KeyCode>>,
+	input_map: Res<'w, InputMap>,
+	gamepads: Res<'w, Gamepads>,
+	gamepad_buttons: Res<'w, ButtonInput<GamepadButton>>,
+	menu_focus: Res<'w, MenuFocus>,
+}
+
+impl ActionInput<'_> {
+	pub fn just_pressed(&self, action: Action) -> bool {
+		self.keys.just_pressed(self.input_map.key(action))
+			|| self.menu_focus.activated == Some(action)
+			|| self.gamepad_buttons(action).any(|button| self.gamepad_buttons.just_pressed(button))
+	}
+
+	pub fn pressed(&self, action: Action) -> bool {
+		self.keys.pressed(self.input_map.key(action))
+			|| self.gamepad_buttons(action).any(|button| self.gamepad_buttons.pressed(button))
+	}
+
+	fn gamepad_buttons(&self, action: Action) -> impl Iterator<Item = GamepadButton> + '_ {
+		let button_type = action.gamepad_button();
+
+		self.gamepads.iter().filter_map(move |gamepad| {
+			button_type.map(|button_type| GamepadButton::new(gamepad, button_type))
+		})
+	}
+}
+
+// Every key press tells what the layout prints on it, kept with the bindings.
+// Shift and the other modifiers give another character on the same key, like
+// the 1 of AZERTY or the ! of QWERTY, so only bare presses are read
+pub fn learn_labels(
+	mut keyboard_events: EventReader<KeyboardInput>,
+	keys: Res<ButtonInput<KeyCode>>,
+	mut input_map: ResMut<InputMap>,
+) {
+	let mut learnt = false;
+	let modified = keys.any_pressed(MODIFIER_KEYS);
+
+	for event in keyboard_events.read() {
+		if event.state == ButtonState::Pressed && !modified {
+			learnt |= input_map.bypass_change_detection().learn_label(event.key_code, &event.logical_key);
+		}
+	}
+
+	// Saved when a key reads differently, not on every press
+	if learnt {
+		input_map.set_changed();
+		input_map.save();
+	}
+}
+
+const MODIFIER_KEYS: [KeyCode; 8] = [
+	KeyCode::ShiftLeft, KeyCode::ShiftRight, KeyCode::ControlLeft, KeyCode::ControlRight,
+	KeyCode::AltLeft, KeyCode::AltRight, KeyCode::SuperLeft, KeyCode::SuperRight,
+];
+
+// Keys that can be bound, KeyCode is only serializable behind a bevy feature.
+// The arrows and Enter are left out, they move through the menus
+pub const BINDABLE_KEYS: [KeyCode; 45] = [
+	KeyCode::KeyA, KeyCode::KeyB, KeyCode::KeyC, KeyCode::KeyD, KeyCode::KeyE,
+	KeyCode::KeyF, KeyCode::KeyG, KeyCode::KeyH, KeyCode::KeyI, KeyCode::KeyJ,
+	KeyCode::KeyK, KeyCode::KeyL, KeyCode::KeyM, KeyCode::KeyN, KeyCode::KeyO,
+	KeyCode::KeyP, KeyCode::KeyQ, KeyCode::KeyR, KeyCode::KeyS, KeyCode::KeyT,
+	KeyCode::KeyU, KeyCode::KeyV, KeyCode::KeyW, KeyCode::KeyX, KeyCode::KeyY,
+	KeyCode::KeyZ, KeyCode::Digit0, KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3,
+	KeyCode::Digit4, KeyCode::Digit5, KeyCode::Digit6, KeyCode::Digit7, KeyCode::Digit8,
+	KeyCode::Digit9, KeyCode::Space, KeyCode::Escape, KeyCode::Tab, KeyCode::Backspace,
+	KeyCode::F1, KeyCode::F2, KeyCode::F3, KeyCode::F4, KeyCode::F5,
+];
+
+fn parse_key(name: &str) -> Option<KeyCode> {
+	BINDABLE_KEYS.iter().find(|key| format!("{:?}", key) == name).copied()
+}
+
+fn key_name(key: KeyCode) -> String {
+	let name = format!("{:?}", key);
+
+	if let Some(letter) = name.strip_prefix("Key") {
+		return letter.to_string();
+	}
+	if let Some(digit) = name.strip_prefix("Digit") {
+		return digit.to_string();
+	}
+
+	name
+}
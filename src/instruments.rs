@@ -0,0 +1,231 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::Player;
+use crate::MainGame;
+use crate::Locale;
+use crate::ActionInput;
+
+use crate::input::Action;
+use crate::settings;
+
+use crate::SKY_RADIUS;
+use crate::MAX_FOV;
+
+const CIRCLE_COLOR: Color = Color::srgb(0.5, 0.9, 0.6);
+// Narrowest field the camera zooms to, in degrees
+const MIN_FIELD: f32 = 0.05;
+
+// Telescopes are given by their focal length and the eyepiece used, binoculars
+// by their magnification and the true field printed on them. Lengths in mm,
+// fields in degrees
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Instrument {
+	Telescope {
+		name: String,
+		focal_length: f32,
+		eyepiece_focal_length: f32,
+		apparent_field: f32,
+	},
+	Binoculars {
+		name: String,
+		magnification: f32,
+		true_field: f32,
+	},
+}
+
+impl Instrument {
+	pub fn name(&self) -> &str {
+		match self {
+			Instrument::Telescope { name, .. } | Instrument::Binoculars { name, .. } => name,
+		}
+	}
+
+	pub fn magnification(&self) -> f32 {
+		match self {
+			Instrument::Telescope { focal_length, eyepiece_focal_length, .. } => focal_length / eyepiece_focal_length,
+			Instrument::Binoculars { magnification, .. } => *magnification,
+		}
+	}
+
+	// Part of the sky seen through it, the apparent field shrunk by the magnification
+	pub fn true_field(&self) -> f32 {
+		match self {
+			Instrument::Telescope { apparent_field, .. } => apparent_field / self.magnification(),
+			Instrument::Binoculars { true_field, .. } => *true_field,
+		}
+	}
+
+	// Lengths, fields and magnification all positive, as a zero or negative one
+	// gives a field the camera cannot show, and the field under half the sky
+	pub fn is_valid(&self) -> bool {
+		let values = match self {
+			Instrument::Telescope { focal_length, eyepiece_focal_length, apparent_field, .. } => vec![*focal_length, *eyepiece_focal_length, *apparent_field],
+			Instrument::Binoculars { magnification, true_field, .. } => vec![*magnification, *true_field],
+		};
+
+		values.into_iter()
+			.chain([self.magnification(), self.true_field()])
+			.all(|value| value.is_finite() && value > 0.0)
+			&& self.true_field() < 180.0
+	}
+}
+
+fn default_instruments() -> Vec<Instrument> {
+	vec![
+		Instrument::Binoculars {
+			name: "10x50 binoculars".into(),
+			magnification: 10.0,
+			true_field: 6.5,
+		},
+		Instrument::Telescope {
+			name: "80/400 refractor, 32 mm eyepiece".into(),
+			focal_length: 400.0,
+			eyepiece_focal_length: 32.0,
+			apparent_field: 50.0,
+		},
+		Instrument::Telescope {
+			name: "200/1200 dobsonian, 25 mm eyepiece".into(),
+			focal_length: 1200.0,
+			eyepiece_focal_length: 25.0,
+			apparent_field: 52.0,
+		},
+		Instrument::Telescope {
+			name: "200/1200 dobsonian, 10 mm eyepiece".into(),
+			focal_length: 1200.0,
+			eyepiece_focal_length: 10.0,
+			apparent_field: 52.0,
+		},
+	]
+}
+
+#[derive(Resource, Default)]
+pub struct Instruments {
+	pub list: Vec<Instrument>,
+	// Field shown in explore mode, if any
+	selected: Option<usize>,
+}
+
+impl Instruments {
+	// Written with a few examples the first time, to be edited by the player
+	pub fn load() -> Self {
+		let mut list : Vec<Instrument> = settings::load("instruments").unwrap_or_else(|| {
+			let list = default_instruments();
+			settings::save("instruments", &list);
+			list
+		});
+
+		list.retain(|instrument| {
+			if !instrument.is_valid() {
+				warn!("instrument {} left out : focal lengths, fields and magnification must be positive, the field under 180°", instrument.name());
+			}
+			instrument.is_valid()
+		});
+
+		Instruments {
+			list,
+			selected: None,
+		}
+	}
+
+	pub fn selected(&self) -> Option<&Instrument> {
+		self.list.get(self.selected?)
+	}
+}
+
+#[derive(Component)]
+pub struct InstrumentLabel;
+
+pub fn setup(
+	mut commands: Commands,
+	mut instruments: ResMut<Instruments>,
+) {
+	// Shows the label again for the instrument kept from the last visit
+	instruments.set_changed();
+
+	let container = commands.spawn((
+		NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				width: Val::Percent(100.0),
+				bottom: Val::Px(60.0),
+				justify_content: JustifyContent::Center,
+				..default()
+			},
+			..default()
+		},
+		MainGame,
+	)).id();
+
+	let label = commands.spawn((
+		TextBundle::from_section("", TextStyle {
+			font_size: 20.0,
+			color: CIRCLE_COLOR,
+			..default()
+		}),
+		InstrumentLabel,
+	)).id();
+
+	commands.entity(container).add_child(label);
+}
+
+// Goes through the instruments then back to the naked eye, the other key zooms
+// so that the field fills the screen height as in the eyepiece
+pub fn select(
+	actions: ActionInput,
+	mut instruments: ResMut<Instruments>,
+	mut player_query: Query<&mut Player>,
+) {
+	if actions.just_pressed(Action::Instrument) && !instruments.list.is_empty() {
+		instruments.selected = match instruments.selected {
+			None => Some(0),
+			Some(index) if index + 1 < instruments.list.len() => Some(index + 1),
+			Some(_) => None,
+		};
+		info!("instrument : {:?}", instruments.selected().map(Instrument::name));
+	}
+
+	if !actions.just_pressed(Action::MatchInstrument) {
+		return;
+	}
+
+	let (Some(instrument), Ok(mut player)) = (instruments.selected(), player_query.get_single_mut()) else {
+		return;
+	};
+
+	// Eyepiece fields are often narrower than the scroll zoom goes, wide
+	// finders wider than the camera shows
+	player.target_fov = Some(instrument.true_field().to_radians().clamp(MIN_FIELD.to_radians(), MAX_FOV));
+	player.zoom_anchor = None;
+}
+
+// The true field as a circle in the middle of the screen
+pub fn show(
+	instruments: Res<Instruments>,
+	locale: Res<Locale>,
+	camera_query: Query<&Transform, With<Player>>,
+	mut label_query: Query<&mut Text, With<InstrumentLabel>>,
+	mut gizmos: Gizmos,
+) {
+	if let (Some(instrument), Ok(transform)) = (instruments.selected(), camera_query.get_single()) {
+		let half_field = instrument.true_field().to_radians() / 2.0;
+		let forward = transform.forward();
+		gizmos.circle(transform.translation + *forward * SKY_RADIUS * half_field.cos(), -forward, SKY_RADIUS * half_field.sin(), CIRCLE_COLOR)
+			.resolution(128);
+	}
+
+	if !instruments.is_changed() && !locale.is_changed() {
+		return;
+	}
+
+	let text = instruments.selected().map(|instrument| locale.text_with("instrument-label", &[
+		("name".into(), instrument.name().to_string()),
+		("magnification".into(), format!("{:.0}", instrument.magnification())),
+		("field".into(), format!("{:.2}°", instrument.true_field())),
+	])).unwrap_or_default();
+
+	for mut label in label_query.iter_mut() {
+		label.sections[0].value = text.clone();
+	}
+}
@@ -0,0 +1,531 @@
+// Bevy systems routinely take many parameters and large query types
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
+use bevy::prelude::*;
+use bevy::math::*;
+use bevy::render::render_resource::PrimitiveTopology;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::view::NoFrustumCulling;
+use bevy::asset::load_internal_asset;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+mod end_state;
+mod start_state;
+mod game_state;
+mod explo_state;
+mod sky_culture;
+mod locale;
+mod info_card;
+mod settings;
+mod input;
+mod bindings_state;
+mod settings_state;
+mod seed;
+mod seed_state;
+mod gamepad;
+mod menu;
+mod audio;
+mod observer;
+mod gyroscope;
+mod catalog;
+mod star_catalog;
+mod atmosphere;
+mod starfield;
+mod sky_labels;
+mod chart;
+mod result_card;
+mod bookmarks;
+mod tour;
+mod tutorial;
+mod measure;
+mod instruments;
+#[cfg(target_arch = "wasm32")]
+mod web;
+
+pub use game_state::GameData;
+pub use game_state::AnswerButton;
+pub use sky_culture::SkyCultures;
+use locale::Locale;
+use locale::Localized;
+use info_card::InfoCard;
+use info_card::ConstellationInfo;
+pub use input::InputMap;
+pub use input::Action;
+use input::ActionInput;
+use menu::MenuFocus;
+use menu::MenuItem;
+pub use observer::Observer;
+use gyroscope::Gyroscope;
+use starfield::StarField;
+use starfield::StarMaterial;
+use starfield::StarParams;
+pub use settings::Settings;
+use audio::SoundEffect;
+pub use seed::GameRng;
+pub use seed::RunMode;
+pub use catalog::CatalogReport;
+pub use catalog::validate_catalog;
+pub use catalog::parse_dec;
+pub use star_catalog::CatalogStar;
+pub use star_catalog::parse_json_catalog;
+pub use star_catalog::encode_catalog;
+pub use star_catalog::decode_catalog;
+pub use star_catalog::convert_catalog;
+pub use star_catalog::star_names;
+pub use chart::ChartProjection;
+pub use chart::constellation_chart;
+pub use result_card::ResultCard;
+pub use bookmarks::Bookmark;
+pub use bookmarks::Bookmarks;
+pub use tour::Tours;
+pub use tour::validate_tours;
+pub use tutorial::Tutorial;
+pub use measure::HandWidths;
+pub use measure::angular_separation;
+pub use measure::hand_widths;
+pub use instruments::Instrument;
+
+const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
+const WRONG_BUTTON: Color = Color::srgb(0.50, 0.15, 0.15);
+const RIGHT_BUTTON: Color = Color::srgb(0.15, 0.50, 0.15);
+const FOCUSED_ITEM: Color = Color::srgb(0.25, 0.25, 0.25);
+
+const EASYNESS: f32 = 1.5;
+const MAX_STAR_SIZE: f32 = 0.63;
+const STAR_SCALE: f32 = 0.02;
+const SKY_RADIUS: f32 = 4.0;
+const CONS_VIEW_RADIUS: f32 = 0.8;
+const MOVE_SPEED: f32 = 12.0;
+const ROT_SPEED: f32 = 9.0;
+const STICK_SPEED: f32 = 1.5;
+const STICK_ROLL_SPEED: f32 = 1.0;
+const TRIGGER_ZOOM_SPEED: f32 = 1.5;
+const MIN_FOV: f32 = 0.02 * std::f32::consts::PI;
+const MAX_FOV: f32 = 0.6 * std::f32::consts::PI;
+const ZOOM_STEP: f32 = 0.9;
+const SCROLL_PIXELS_PER_LINE: f32 = 100.0;
+// Stars shrink over the last magnitudes before the limit rather than popping out
+const STAR_FADE_RANGE: f32 = 0.5;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct StarData {
+	#[serde(rename = "Dec")]
+    dec: String,
+    #[serde(rename = "HR")]
+    hr: String,
+    #[serde(rename = "K")]
+    k: Option<String>,
+    #[serde(rename = "RA")]
+    ra: String,
+    #[serde(rename = "V")]
+    v: String,
+    #[serde(rename = "C")]
+    constellation: Option<String>,  
+    #[serde(rename = "F")]
+    f: Option<String>,
+    #[serde(rename = "B")]
+    bayer_designation: Option<String>,
+    #[serde(rename = "N")]
+    name: Option<String>,
+}
+
+#[derive(Resource, Default, Clone)]
+struct Sky {
+    content: Vec<Constellation>,
+}
+
+impl Sky {
+	fn as_string(&self) -> Vec<String> {
+		let mut cons_names : Vec<String> = vec![];
+		for cons in self.content.clone() {
+			cons_names.push(cons.name.clone());
+		}
+		cons_names
+	}
+
+	fn get_constellation(&self, name: &str) -> Constellation {
+		for cons in self.content.clone() {
+			if cons.name == name {
+				return cons;
+			}
+		}
+		self.content[0].clone()
+	} 
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Constellation {
+	#[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "RAh")]
+    rah: f64,
+    #[serde(rename = "DEd")]
+    dec: f64,
+    stars: Vec<StarPos>,
+    lines: Vec<[u32; 2]>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct StarPos {
+	id: usize,
+	#[serde(rename = "bfID")]
+	bfid: String,
+	#[serde(rename = "RAh")]
+	rah: f64,              
+	#[serde(rename = "DEd")]
+	dec: f64,               
+}
+
+#[derive(Component)]
+struct ConstellationModel {
+	name: String,
+	center: Vec3,
+}
+
+#[derive(Component)]
+struct StartMenu;
+
+#[derive(Component)]
+struct MainGame;
+
+#[derive(Component)]
+struct GameOver;
+
+#[derive(Component)]
+struct KeyBindingsMenu;
+
+#[derive(Component)]
+struct SettingsMenu;
+
+#[derive(Component)]
+struct SeedEntryMenu;
+
+#[derive(Component, Default)]
+struct Player {
+	target_rotation: Option<Quat>,
+	r_drag_pos: Option<Vec2>,
+	l_drag_pos: Option<Vec2>,
+	stick_moving: bool,
+	touch_moving: bool,
+	target_fov: Option<f32>,
+	zoom_anchor: Option<Vec2>,
+}
+
+impl Player {
+	// Zoom sources only set a target, zoom_camera animates the field of view
+	fn zoom(&mut self, current_fov: f32, factor: f32, anchor: Option<Vec2>) {
+		// Instrument fields can be narrower than the zoom goes, zooming in then stays there
+		let min_fov = MIN_FOV.min(current_fov);
+		self.target_fov = Some((self.target_fov.unwrap_or(current_fov) * factor).clamp(min_fov, MAX_FOV));
+		self.zoom_anchor = anchor;
+	}
+}
+
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
+pub enum GameState {
+    #[default]
+    Start,
+    Explo,
+    Game,
+    End,
+    Bindings,
+    Settings,
+    SeedEntry,
+    Tour,
+    Tutorial,
+}
+
+// Everything the desktop and web builds run
+pub fn run() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((GamePlugin, ViewPlugin, SoundPlugin))
+        .run();
+}
+
+// Screens, quiz and data. Needs no window nor GPU, only the input and state
+// plugins on top of MinimalPlugins. Settings and bindings inserted before
+// the plugin are kept and nothing is saved, otherwise the saved ones are loaded
+pub struct GamePlugin;
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        if app.world().contains_resource::<Settings>() {
+            settings::keep_in_memory();
+        } else {
+            app.insert_resource(Settings::load());
+        }
+        if !app.world().contains_resource::<InputMap>() {
+            app.insert_resource(InputMap::load());
+        }
+        let language = app.world().resource::<Settings>().language;
+
+        // Already there with the render plugins, plain storage without them
+        app
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<StandardMaterial>>()
+            .init_resource::<Assets<StarMaterial>>()
+            .insert_resource(Sky::default())
+            .insert_resource(GameData::default())
+            .init_resource::<SkyCultures>()
+            .init_resource::<Tours>()
+            .init_resource::<Tutorial>()
+            .insert_resource(Locale::new(language))
+            .init_resource::<settings_state::SettingsScreen>()
+            .add_event::<SoundEffect>()
+            .init_resource::<GameRng>()
+            .init_resource::<seed_state::SeedEntry>()
+            .init_resource::<ConstellationInfo>()
+            .init_resource::<InfoCard>()
+            .init_resource::<bindings_state::BindingsMenu>()
+            .init_resource::<MenuFocus>()
+            .init_state::<GameState>()
+            .add_systems(Startup, star_setup)
+            .add_systems(Startup, sky_culture::setup)
+            .add_systems(Update, sky_culture::apply_selection.run_if(resource_changed::<SkyCultures>))
+            .add_systems(PreUpdate, input::learn_labels.after(bevy::input::InputSystem))
+            .add_systems(PreUpdate, (menu::menu_navigation, menu::menu_pointer.after(bevy::ui::UiSystem::Focus)).chain().after(bevy::input::InputSystem))
+            // Letters typed in a seed code or pressed to rebind an action are not shortcuts
+            .add_systems(Update, locale::switch_language.run_if(not(in_state(GameState::SeedEntry))).run_if(not(in_state(GameState::Bindings))))
+            .add_systems(Update, locale::follow_settings.run_if(resource_changed::<Settings>))
+            .add_systems(Update, locale::update_texts.after(locale::follow_settings))
+            .add_systems(Update, sky_glow.run_if(resource_changed::<Settings>))
+            .add_systems(Update, info_card::update_card.run_if(in_state(GameState::Game).or_else(in_state(GameState::Explo))))
+            .add_systems(OnEnter(GameState::Start), start_state::setup)
+            .add_systems(OnEnter(GameState::Start), sky_culture::restore_selection.before(start_state::setup))
+            .add_systems(Update, start_state::player_interact.run_if(in_state(GameState::Start)).before(sky_culture::apply_selection))
+            .add_systems(Update, start_state::menu_labels.run_if(in_state(GameState::Start)))
+            .add_systems(OnExit(GameState::Start), despawn_screen::<StartMenu>)
+            .add_systems(OnEnter(GameState::Game), game_state::setup)
+            .add_systems(Update, game_state::player_interact.run_if(in_state(GameState::Game)))
+            .add_systems(Update, game_state::ui_buttons.run_if(in_state(GameState::Game)))
+            .add_systems(Update, game_state::ui_labels.run_if(in_state(GameState::Game)))
+            .add_systems(OnExit(GameState::Game), despawn_screen::<MainGame>)
+            .add_systems(OnEnter(GameState::End), end_state::setup)
+            .add_systems(Update, end_state::player_interact.run_if(in_state(GameState::End)))
+            .add_systems(Update, result_card::save.run_if(in_state(GameState::End)))
+            .add_systems(OnExit(GameState::End), despawn_screen::<GameOver>)
+            .add_systems(OnEnter(GameState::Explo), explo_state::setup)
+            .add_systems(Update, explo_state::player_interact.run_if(in_state(GameState::Explo)))
+            .add_systems(OnExit(GameState::Explo), despawn_screen::<MainGame>)
+            .add_systems(OnEnter(GameState::Tour), tour::setup)
+            .add_systems(Update, tour::play.run_if(in_state(GameState::Tour)))
+            .add_systems(OnExit(GameState::Tour), despawn_screen::<MainGame>)
+            .add_systems(OnEnter(GameState::Tutorial), tutorial::setup)
+            .add_systems(Update, tutorial::progress.run_if(in_state(GameState::Tutorial).or_else(in_state(GameState::Game))))
+            .add_systems(OnExit(GameState::Tutorial), despawn_screen::<MainGame>)
+            .add_systems(OnEnter(GameState::Game), tutorial::game_setup.after(game_state::setup))
+            .add_systems(OnExit(GameState::Game), tutorial::game_exit)
+            .add_systems(OnEnter(GameState::Bindings), bindings_state::setup)
+            .add_systems(Update, bindings_state::player_interact.run_if(in_state(GameState::Bindings)))
+            .add_systems(Update, bindings_state::ui_labels.run_if(in_state(GameState::Bindings)))
+            .add_systems(OnExit(GameState::Bindings), despawn_screen::<KeyBindingsMenu>)
+            .add_systems(OnEnter(GameState::Settings), settings_state::setup)
+            .add_systems(Update, settings_state::player_interact.run_if(in_state(GameState::Settings)))
+            .add_systems(Update, settings_state::ui_labels.run_if(in_state(GameState::Settings)))
+            .add_systems(OnExit(GameState::Settings), despawn_screen::<SettingsMenu>)
+            .add_systems(OnEnter(GameState::SeedEntry), seed_state::setup)
+            .add_systems(Update, seed_state::player_interact.run_if(in_state(GameState::SeedEntry)).before(sky_culture::apply_selection))
+            .add_systems(Update, seed_state::ui_labels.run_if(in_state(GameState::SeedEntry)))
+            .add_systems(OnExit(GameState::SeedEntry), despawn_screen::<SeedEntryMenu>);
+    }
+}
+
+// Star rendering, sky labels, angle measures, instrument fields, chart export, screenshots, bookmarks and links, camera moves, pointer hovering and the gyroscope, which need a window
+pub struct ViewPlugin;
+
+impl Plugin for ViewPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, starfield::STAR_SHADER, "../data/shaders/stars.wgsl", Shader::from_wgsl);
+
+        app
+            .insert_resource(Observer::load())
+            .init_resource::<Gyroscope>()
+            .insert_resource(bookmarks::Bookmarks::load())
+            .init_resource::<measure::Measure>()
+            .insert_resource(instruments::Instruments::load())
+            .add_plugins(MaterialPlugin::<StarMaterial> {
+                prepass_enabled: false,
+                shadows_enabled: false,
+                ..default()
+            })
+            .add_systems(Startup, atmosphere::setup)
+            .add_systems(Update, starfield::update_params)
+            .add_systems(Update, atmosphere::horizon_glow)
+            .add_systems(Update, explo_state::player_mouse_move.run_if(in_state(GameState::Game).or_else(in_state(GameState::Explo)).or_else(in_state(GameState::Tutorial))))
+            .add_systems(Update, explo_state::player_mouse_rotate.run_if(in_state(GameState::Game).or_else(in_state(GameState::Explo)).or_else(in_state(GameState::Tutorial))))
+            .add_systems(Update, explo_state::rotate_camera.run_if(in_state(GameState::Game).or_else(in_state(GameState::Explo)).or_else(in_state(GameState::Tour)).or_else(in_state(GameState::Tutorial))))
+            .add_systems(Update, explo_state::zoom.run_if(in_state(GameState::Game).or_else(in_state(GameState::Explo)).or_else(in_state(GameState::Tutorial))))
+            .add_systems(Update, explo_state::zoom_camera.run_if(in_state(GameState::Game).or_else(in_state(GameState::Explo)).or_else(in_state(GameState::Tour)).or_else(in_state(GameState::Tutorial))))
+            .add_systems(Update, gamepad::camera_sticks.run_if(in_state(GameState::Game).or_else(in_state(GameState::Explo)).or_else(in_state(GameState::Tutorial))))
+            .add_systems(Update, explo_state::player_touch.run_if(in_state(GameState::Game).or_else(in_state(GameState::Explo)).or_else(in_state(GameState::Tutorial))))
+            .add_systems(Update, explo_state::constellation_opacity.run_if(in_state(GameState::Explo)))
+            .add_systems(Update, gyroscope::toggle.run_if(in_state(GameState::Explo)))
+            .add_systems(OnEnter(GameState::Explo), sky_labels::setup.after(explo_state::setup))
+            .add_systems(Update, sky_labels::toggle.run_if(in_state(GameState::Explo)))
+            .add_systems(OnEnter(GameState::Explo), measure::setup)
+            .add_systems(Update, measure::pick.run_if(in_state(GameState::Explo)))
+            .add_systems(Update, measure::show.run_if(in_state(GameState::Explo)).after(measure::pick))
+            .add_systems(OnEnter(GameState::Explo), instruments::setup)
+            .add_systems(Update, instruments::select.run_if(in_state(GameState::Explo)))
+            .add_systems(Update, instruments::show.run_if(in_state(GameState::Explo)).after(instruments::select).after(explo_state::rotate_camera))
+            .add_systems(Update, result_card::screenshot)
+            .add_systems(Update, bookmarks::open_link.run_if(run_once()).after(sky_culture::apply_selection))
+            .add_systems(Update, bookmarks::save_view.run_if(in_state(GameState::Explo)))
+            .add_systems(Update, bookmarks::restore_view.run_if(in_state(GameState::Explo)))
+            // The quiz keeps its answer off the charts, whose labels would name it
+            .add_systems(Update, chart::export_view.run_if(in_state(GameState::Explo)))
+            .add_systems(Update, sky_labels::place_labels.run_if(in_state(GameState::Explo)).after(explo_state::zoom_camera).after(explo_state::rotate_camera))
+            .add_systems(Update, gyroscope::point_camera.run_if(in_state(GameState::Explo)).before(explo_state::rotate_camera));
+
+        #[cfg(target_arch = "wasm32")]
+        app.add_systems(Update, bookmarks::update_link.run_if(in_state(GameState::Explo)));
+    }
+}
+
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<audio::SoundEffects>()
+            .add_systems(Startup, audio::setup)
+            .add_systems(Update, audio::toggle_mute.run_if(not(in_state(GameState::SeedEntry))).run_if(not(in_state(GameState::Bindings))))
+            .add_systems(Update, audio::music_volume.run_if(resource_changed::<Settings>))
+            .add_systems(Update, audio::play_effects);
+    }
+}
+
+fn spawn_cons_lines(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    target_constellation: Constellation,
+    brightness: f32,
+) {
+    let line_material = materials.add(StandardMaterial {
+        emissive: LinearRgba::rgb(0.5, 0.5, 1.0) * brightness,
+        alpha_mode: AlphaMode::Blend,
+        ..default()
+    });
+    
+    let mut vertices : Vec<Vec3> = vec![];
+
+    let mut avg_pos : Vec3 = Vec3::ZERO;
+
+    for line in target_constellation.lines {
+    	let (Some(start), Some(end)) = (target_constellation.stars.get(line[0] as usize), target_constellation.stars.get(line[1] as usize)) else {
+    		warn!("{} : line {:?} uses a missing star, run astraea-data", target_constellation.name, line);
+    		continue;
+    	};
+    	for star in [start, end] {
+    		let star_pos = celestial_to_cartesian(star.rah, star.dec);
+    		vertices.push(star_pos);
+    		avg_pos += star_pos;
+    	}
+    }
+
+    avg_pos /= vertices.len().max(1) as f32;
+
+    let mut mesh = Mesh::new(PrimitiveTopology::LineList, RenderAssetUsages::RENDER_WORLD);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
+    
+    commands.spawn((
+	    PbrBundle {
+	        mesh: meshes.add(mesh),
+	        material: line_material.clone(),
+	        transform: Transform::default(),
+	        ..default()
+	    },
+	 	ConstellationModel {
+	 		name: target_constellation.name,
+	 		center: avg_pos,
+	 	},
+	 	MainGame
+	));
+}
+
+fn star_setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StarMaterial>>,
+    settings: Res<Settings>,
+) {
+    commands.insert_resource(ClearColor(Color::BLACK));
+
+	let stars : Vec<(Vec3, f32, bool, f32)> = star_catalog::load_catalog().into_iter()
+		.map(|star| (
+			celestial_to_cartesian(star.rah as f64, star.dec as f64) * SKY_RADIUS,
+			star.magnitude,
+			star.in_constellation,
+			star.color_index,
+		))
+		.collect();
+
+	let material = materials.add(StarMaterial {
+		params: StarParams::new(&settings, None, 0.0),
+	});
+
+	commands.spawn((
+		MaterialMeshBundle {
+			mesh: meshes.add(starfield::star_mesh(&stars)),
+			material: material.clone(),
+			..default()
+		},
+		// The quads are spread around the star positions in the shader
+		NoFrustumCulling,
+	));
+
+    commands.insert_resource(StarField { material });
+
+    commands.spawn((
+       	Camera3dBundle {
+   	        transform: Transform::from_xyz(0.0, 0.0, 0.0),
+   	        ..default()
+   	    },
+   	    Player::default(),
+   	));
+}
+
+fn load_star_catalog() -> Vec<StarData> {
+	serde_json::from_str(include_str!("../data/stars.json")).expect("no star json provided")
+}
+
+fn star_coordinates(star_data: StarData) -> (f64, f64) {
+	let rah = catalog::parse_ra(&star_data.ra).expect("invalid right ascension, run astraea-data");
+	let dec = catalog::parse_dec(&star_data.dec).expect("invalid declination, run astraea-data");
+
+	(rah, dec)
+}
+
+fn celestial_to_cartesian(rah: f64, ded: f64) -> Vec3 {
+    let y_rot = 2.0 * PI * rah / 24.0;
+    let x_rot = 2.0 * PI * ded / 360.0;
+
+    let x : f32 = (y_rot.sin() * x_rot.cos()) as f32;
+    let y : f32 = x_rot.sin() as f32;
+    let z : f32 = (y_rot.cos() * x_rot.cos()) as f32;
+
+    Vec3::new(x, y, z)
+}
+
+// Light pollution washes the black sky out along with the faint stars
+fn sky_glow(
+	settings: Res<Settings>,
+	mut clear_color: ResMut<ClearColor>,
+) {
+	let glow = settings.light_pollution();
+
+	clear_color.0 = Color::srgb(0.09 * glow, 0.08 * glow, 0.07 * glow);
+}
+
+// Generic system that takes a component as a parameter, and will despawn all entities with that component
+fn despawn_screen<T: Component>(to_despawn: Query<Entity, With<T>>, mut commands: Commands) {
+    for entity in &to_despawn {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
@@ -0,0 +1,219 @@
+use bevy::prelude::*;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use unic_langid::LanguageIdentifier;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::ActionInput;
+use crate::Settings;
+
+use crate::input::Action;
+
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Language {
+	#[default]
+	English,
+	French,
+}
+
+impl Language {
+	const ALL: [Language; 2] = [Language::English, Language::French];
+
+	fn id(&self) -> &'static str {
+		match self {
+			Language::English => "en",
+			Language::French => "fr",
+		}
+	}
+
+	fn source(&self) -> &'static str {
+		match self {
+			Language::English => include_str!("../data/locales/en.ftl"),
+			Language::French => include_str!("../data/locales/fr.ftl"),
+		}
+	}
+
+	pub fn next(&self) -> Language {
+		let index = Language::ALL.iter().position(|language| language == self).unwrap_or(0);
+		Language::ALL[(index + 1) % Language::ALL.len()]
+	}
+
+	pub fn previous(&self) -> Language {
+		let index = Language::ALL.iter().position(|language| language == self).unwrap_or(0);
+		Language::ALL[(index + Language::ALL.len() - 1) % Language::ALL.len()]
+	}
+}
+
+// Texts written in the data files rather than in fluent, by language id
+pub type Texts = BTreeMap<String, String>;
+
+#[derive(Resource)]
+pub struct Locale {
+	pub language: Language,
+	bundles: Vec<(Language, FluentBundle<FluentResource>)>,
+}
+
+impl Default for Locale {
+	fn default() -> Self {
+		Locale::new(Language::default())
+	}
+}
+
+impl Locale {
+	pub fn new(language: Language) -> Self {
+		let mut bundles = vec![];
+
+		for language in Language::ALL {
+			let lang_id : LanguageIdentifier = language.id().parse().expect("invalid language identifier");
+			let resource = FluentResource::try_new(language.source().to_string()).expect("invalid fluent file");
+
+			let mut bundle = FluentBundle::new_concurrent(vec![lang_id]);
+			// Isolation marks show up as boxes with the default font
+			bundle.set_use_isolating(false);
+			bundle.add_resource(resource).expect("duplicated fluent message");
+
+			bundles.push((language, bundle));
+		}
+
+		Locale {
+			language,
+			bundles,
+		}
+	}
+
+	pub fn text(&self, key: &str) -> String {
+		self.text_with(key, &[])
+	}
+
+	pub fn text_with(&self, key: &str, args: &[(String, String)]) -> String {
+		self.lookup(key, args).unwrap_or_else(|| key.to_string())
+	}
+
+	// Constellations are keyed by their english (or latin) name, which is
+	// also what gets displayed when no translation exists
+	pub fn constellation(&self, name: &str) -> String {
+		self.lookup(&format!("cons-{}", slug(name)), &[]).unwrap_or_else(|| name.to_string())
+	}
+
+	pub fn constellation_description(&self, name: &str, description: &str) -> String {
+		self.lookup(&format!("cons-{}-description", slug(name)), &[]).unwrap_or_else(|| description.to_string())
+	}
+
+	pub fn culture(&self, name: &str) -> String {
+		self.lookup(&format!("culture-{}", slug(name)), &[]).unwrap_or_else(|| name.to_string())
+	}
+
+	pub fn culture_description(&self, name: &str, description: &str) -> String {
+		self.lookup(&format!("culture-{}-description", slug(name)), &[]).unwrap_or_else(|| description.to_string())
+	}
+
+	fn lookup(&self, key: &str, args: &[(String, String)]) -> Option<String> {
+		let mut fluent_args = FluentArgs::new();
+		for (name, value) in args {
+			fluent_args.set(name.clone(), value.clone());
+		}
+
+		// Fall back on english before giving up on the key
+		for language in [self.language, Language::English] {
+			let Some((_, bundle)) = self.bundles.iter().find(|(bundle_language, _)| *bundle_language == language) else {
+				continue;
+			};
+			let Some(pattern) = bundle.get_message(key).and_then(|message| message.value()) else {
+				continue;
+			};
+
+			let mut errors = vec![];
+			let value = bundle.format_pattern(pattern, Some(&fluent_args), &mut errors);
+
+			for error in errors {
+				warn!("{} : {}", key, error);
+			}
+
+			return Some(value.to_string());
+		}
+
+		None
+	}
+}
+
+// Falls back on english, then on any language
+pub fn localized<'a>(texts: &'a Texts, locale: &Locale) -> &'a str {
+	texts.get(locale.language.id())
+		.or_else(|| texts.get("en"))
+		.or_else(|| texts.values().next())
+		.map(String::as_str)
+		.unwrap_or("")
+}
+
+// Text whose content comes from a fluent message, refreshed when the language changes
+#[derive(Component)]
+pub struct Localized {
+	key: String,
+	args: Vec<(String, String)>,
+}
+
+impl Localized {
+	pub fn new(key: &str) -> Self {
+		Localized {
+			key: key.into(),
+			args: vec![],
+		}
+	}
+
+	pub fn with_arg(mut self, name: &str, value: String) -> Self {
+		self.args.push((name.into(), value));
+		self
+	}
+
+	pub fn text(&self, locale: &Locale) -> String {
+		locale.text_with(&self.key, &self.args)
+	}
+}
+
+pub fn update_texts(
+	locale: Res<Locale>,
+	mut text_query: Query<(Ref<Localized>, &mut Text)>,
+) {
+	for (localized, mut text) in text_query.iter_mut() {
+		if !locale.is_changed() && !localized.is_added() {
+			continue;
+		}
+		text.sections[0].value = localized.text(&locale);
+	}
+}
+
+// The language is a setting, the locale follows it
+pub fn switch_language(
+	actions: ActionInput,
+	mut settings: ResMut<Settings>,
+) {
+	if actions.just_pressed(Action::ChangeLanguage) {
+		settings.language = settings.language.next();
+		settings.save();
+		info!("language : {:?}", settings.language);
+	}
+}
+
+pub fn follow_settings(
+	settings: Res<Settings>,
+	mut locale: ResMut<Locale>,
+) {
+	if locale.language != settings.language {
+		locale.language = settings.language;
+	}
+}
+
+fn slug(name: &str) -> String {
+	let mut slug = String::new();
+
+	for c in name.to_lowercase().chars() {
+		if c.is_ascii_alphanumeric() {
+			slug.push(c);
+		} else if !slug.is_empty() && !slug.ends_with('-') {
+			slug.push('-');
+		}
+	}
+
+	slug.trim_end_matches('-').to_string()
+}
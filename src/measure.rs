@@ -0,0 +1,230 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::Player;
+use crate::MainGame;
+use crate::Locale;
+use crate::ActionInput;
+use crate::InputMap;
+use crate::Settings;
+
+use crate::input::Action;
+use crate::chart::celestial_coordinates;
+
+use crate::celestial_to_cartesian;
+use crate::star_catalog;
+
+use crate::SKY_RADIUS;
+use crate::MAX_FOV;
+
+// Pixels around the cursor a star is picked from rather than the bare sky
+const SNAP_DISTANCE: f32 = 12.0;
+// Drawn a bit inside the sky sphere, in front of the stars and figures
+const ARC_RADIUS: f32 = 0.98 * SKY_RADIUS;
+const ARC_COLOR: Color = Color::srgb(1.0, 0.8, 0.3);
+
+// Sizes at arm's length, the usual rule of thumb for star hopping
+const FINGER_DEGREES: f32 = 1.0;
+const FIST_DEGREES: f32 = 10.0;
+
+#[derive(Clone, Debug)]
+struct MeasurePoint {
+	direction: Vec3,
+	// Name of the star picked, if any
+	name: Option<String>,
+}
+
+impl MeasurePoint {
+	fn name(&self) -> String {
+		self.name.clone().unwrap_or_else(|| {
+			let (rah, dec) = celestial_coordinates(self.direction);
+			let minutes = (rah * 60.0).round() as u32;
+			format!("{}h{:02}m {:+.1}°", minutes / 60 % 24, minutes % 60, dec)
+		})
+	}
+}
+
+// The two ends of the angle being measured, the second press starts the next one
+#[derive(Resource, Default)]
+pub struct Measure {
+	points: Vec<MeasurePoint>,
+}
+
+#[derive(Component)]
+pub struct MeasureLabel;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum HandWidths {
+	Fingers(u32),
+	Fists(f32),
+}
+
+// Great circle separation between two places of the sky, in degrees
+pub fn angular_separation(rah_1: f64, dec_1: f64, rah_2: f64, dec_2: f64) -> f32 {
+	separation(celestial_to_cartesian(rah_1, dec_1), celestial_to_cartesian(rah_2, dec_2))
+}
+
+// Steadier than the arc cosine of the dot product for close and opposite points
+fn separation(a: Vec3, b: Vec3) -> f32 {
+	a.cross(b).length().atan2(a.dot(b)).to_degrees()
+}
+
+// Fingers under a fist, fists to the half above
+pub fn hand_widths(degrees: f32) -> HandWidths {
+	if degrees < FIST_DEGREES * 0.95 {
+		HandWidths::Fingers((degrees / FINGER_DEGREES).round() as u32)
+	} else {
+		HandWidths::Fists((degrees / FIST_DEGREES * 2.0).round() / 2.0)
+	}
+}
+
+fn hand_text(degrees: f32, locale: &Locale) -> String {
+	match hand_widths(degrees) {
+		HandWidths::Fingers(0) => locale.text("measure-less-than-finger"),
+		HandWidths::Fingers(1) => locale.text("measure-finger"),
+		HandWidths::Fingers(count) => locale.text_with("measure-fingers", &[("count".into(), count.to_string())]),
+		HandWidths::Fists(count) if count <= 1.0 => locale.text("measure-fist"),
+		HandWidths::Fists(count) => locale.text_with("measure-fists", &[("count".into(), count.to_string())]),
+	}
+}
+
+pub fn setup(
+	mut commands: Commands,
+	mut measure: ResMut<Measure>,
+) {
+	measure.points.clear();
+
+	let container = commands.spawn((
+		NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				width: Val::Percent(100.0),
+				bottom: Val::Px(30.0),
+				justify_content: JustifyContent::Center,
+				..default()
+			},
+			..default()
+		},
+		MainGame,
+	)).id();
+
+	let label = commands.spawn((
+		TextBundle::from_section("", TextStyle {
+			font_size: 20.0,
+			color: ARC_COLOR,
+			..default()
+		}),
+		MeasureLabel,
+	)).id();
+
+	commands.entity(container).add_child(label);
+}
+
+// Marks the point under the cursor, or the middle of the screen without one, the
+// nearest star shown around it if there is one
+pub fn pick(
+	actions: ActionInput,
+	settings: Res<Settings>,
+	mut measure: ResMut<Measure>,
+	camera_query: Query<(&Camera, &GlobalTransform), With<Player>>,
+	window_query: Query<&Window, With<PrimaryWindow>>,
+	mut stars: Local<Vec<(Option<String>, Vec3, f32)>>,
+) {
+	if !actions.just_pressed(Action::Measure) {
+		return;
+	}
+
+	let (Ok((camera, camera_transform)), Ok(window)) = (camera_query.get_single(), window_query.get_single()) else {
+		return;
+	};
+
+	let cursor = window.cursor_position().unwrap_or(Vec2::new(window.width(), window.height()) / 2.0);
+	let Some(ray) = camera.viewport_to_world(camera_transform, cursor) else {
+		return;
+	};
+
+	if stars.is_empty() {
+		let catalog = star_catalog::load_catalog();
+		let names = star_catalog::star_names(&catalog);
+		*stars = catalog.into_iter().zip(names)
+			.map(|(star, name)| (name, celestial_to_cartesian(star.rah as f64, star.dec as f64), star.magnitude))
+			.collect();
+	}
+
+	let nearest_star = stars.iter()
+		.filter(|(_, _, magnitude)| *magnitude <= settings.limiting_magnitude)
+		.filter_map(|(name, direction, _)| {
+			let position = camera.world_to_viewport(camera_transform, *direction * SKY_RADIUS)?;
+			Some((name, direction, position.distance(cursor)))
+		})
+		.filter(|(_, _, distance)| *distance < SNAP_DISTANCE)
+		.min_by(|a, b| a.2.total_cmp(&b.2));
+
+	let point = match nearest_star {
+		Some((name, direction, _)) => MeasurePoint { direction: *direction, name: name.clone() },
+		None => MeasurePoint { direction: *ray.direction, name: None },
+	};
+
+	if measure.points.len() >= 2 {
+		measure.points.clear();
+	}
+	info!("measure point : {}", point.name());
+	measure.points.push(point);
+}
+
+// The arc between the two points and the angle, told in degrees and hand widths
+pub fn show(
+	measure: Res<Measure>,
+	locale: Res<Locale>,
+	input_map: Res<InputMap>,
+	camera_query: Query<&Projection, With<Player>>,
+	mut label_query: Query<&mut Text, With<MeasureLabel>>,
+	mut gizmos: Gizmos,
+) {
+	let fov = match camera_query.get_single() {
+		Ok(Projection::Perspective(perspective)) => perspective.fov,
+		_ => MAX_FOV,
+	};
+	// Markers keep the same size on screen whatever the zoom
+	let marker_radius = ARC_RADIUS * fov * 0.01;
+
+	for point in measure.points.iter() {
+		let position = point.direction * ARC_RADIUS;
+		if let Ok(normal) = Dir3::new(-point.direction) {
+			gizmos.circle(position, normal, marker_radius, ARC_COLOR);
+		}
+	}
+
+	if let [start, end] = &measure.points[..] {
+		let rotation = Quat::from_rotation_arc(start.direction, end.direction);
+		let segments = (separation(start.direction, end.direction) as usize).max(8);
+		gizmos.linestrip((0..=segments).map(|index| {
+			Quat::IDENTITY.slerp(rotation, index as f32 / segments as f32) * start.direction * ARC_RADIUS
+		}), ARC_COLOR);
+	}
+
+	if !measure.is_changed() && !locale.is_changed() {
+		return;
+	}
+
+	let text = match &measure.points[..] {
+		[] => String::new(),
+		[start] => locale.text_with("measure-first", &[
+			("from".into(), start.name()),
+			("key".into(), input_map.key_name(Action::Measure)),
+		]),
+		[start, end, ..] => {
+			let degrees = separation(start.direction, end.direction);
+			locale.text_with("measure-angle", &[
+				("from".into(), start.name()),
+				("to".into(), end.name()),
+				("angle".into(), format!("{:.1}°", degrees)),
+				("hands".into(), hand_text(degrees, &locale)),
+			])
+		}
+	};
+
+	for mut label in label_query.iter_mut() {
+		label.sections[0].value = text.clone();
+	}
+}
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+
+use crate::input::Action;
+
+use crate::FOCUSED_ITEM;
+
+// Menu line that can be focused with the d-pad and activated with South
+#[derive(Component)]
+pub struct MenuItem {
+	pub action: Action,
+	pub order: usize,
+}
+
+// Menu line leading to a screen only usable with a keyboard, left out of the
+// d-pad focus but still clicked or tapped
+#[derive(Component)]
+pub struct KeyboardOnly;
+
+#[derive(Resource, Default)]
+pub struct MenuFocus {
+	focused: Option<usize>,
+	pub activated: Option<Action>,
+}
+
+pub fn menu_navigation(
+	gamepads: Res<Gamepads>,
+	buttons: Res<ButtonInput<GamepadButton>>,
+	mut menu_focus: ResMut<MenuFocus>,
+	mut item_query: Query<(&MenuItem, &mut BackgroundColor), Without<KeyboardOnly>>,
+) {
+	if menu_focus.activated.is_some() {
+		menu_focus.activated = None;
+	}
+
+	// Orders are those of the whole menu, the focus steps through the ones left
+	let mut orders : Vec<usize> = item_query.iter().map(|(item, _)| item.order).collect();
+	orders.sort();
+	let item_count = orders.len();
+
+	if item_count == 0 {
+		if menu_focus.focused.is_some() {
+			menu_focus.focused = None;
+		}
+		return;
+	}
+
+	let just_pressed = |button_type: GamepadButtonType| {
+		gamepads.iter().any(|gamepad| buttons.just_pressed(GamepadButton::new(gamepad, button_type)))
+	};
+
+	if just_pressed(GamepadButtonType::DPadDown) {
+		menu_focus.focused = Some(match menu_focus.focused {
+			Some(focused) => (focused + 1) % item_count,
+			None => 0,
+		});
+	}
+
+	if just_pressed(GamepadButtonType::DPadUp) {
+		menu_focus.focused = Some(match menu_focus.focused {
+			Some(focused) => (focused + item_count - 1) % item_count,
+			None => item_count - 1,
+		});
+	}
+
+	let focused = menu_focus.focused.map(|focused| orders[focused.min(item_count - 1)]);
+
+	for (item, mut background_color) in item_query.iter_mut() {
+		let is_focused = Some(item.order) == focused;
+
+		*background_color = if is_focused {
+			FOCUSED_ITEM.into()
+		} else {
+			Color::NONE.into()
+		};
+
+		if is_focused && just_pressed(GamepadButtonType::South) {
+			menu_focus.activated = Some(item.action);
+		}
+	}
+}
+
+// Clicking or tapping a menu line activates it
+pub fn menu_pointer(
+	mut menu_focus: ResMut<MenuFocus>,
+	item_query: Query<(&Interaction, &MenuItem), Changed<Interaction>>,
+) {
+	for (interaction, item) in item_query.iter() {
+		if *interaction == Interaction::Pressed {
+			menu_focus.activated = Some(item.action);
+		}
+	}
+}
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+use crate::settings;
+use crate::seed::unix_time;
+
+// Where the sky is watched from, in degrees, north and east positive
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct Observer {
+	pub latitude: f64,
+	pub longitude: f64,
+	// Unix time the sky is shown at, now unless a bookmark says otherwise
+	#[serde(skip)]
+	pub time: Option<f64>,
+}
+
+impl Default for Observer {
+	fn default() -> Self {
+		// Lille, where the game jam took place
+		Observer {
+			latitude: 50.63,
+			longitude: 3.06,
+			time: None,
+		}
+	}
+}
+
+impl Observer {
+	pub fn load() -> Self {
+		settings::load("observer").unwrap_or_default()
+	}
+
+	pub fn time(&self) -> f64 {
+		self.time.unwrap_or_else(unix_time)
+	}
+
+	// Local sidereal time in hours, the right ascension crossing the meridian
+	pub fn sidereal_time(&self, unix_time: f64) -> f64 {
+		let days_since_j2000 = unix_time / 86400.0 + 2440587.5 - 2451545.0;
+		let greenwich = 18.697374558 + 24.065709824419 * days_since_j2000;
+
+		(greenwich + self.longitude / 15.0).rem_euclid(24.0)
+	}
+
+	// Rotation taking the east / north / up frame of the observer to the
+	// sky, which has the celestial pole on y and 0h of right ascension on z
+	pub fn horizon_to_sky(&self, unix_time: f64) -> Quat {
+		let theta = 2.0 * PI * self.sidereal_time(unix_time) / 24.0;
+		let phi = self.latitude.to_radians();
+
+		let east = Vec3::new(theta.cos() as f32, 0.0, -theta.sin() as f32);
+		let north = Vec3::new((-phi.sin() * theta.sin()) as f32, phi.cos() as f32, (-phi.sin() * theta.cos()) as f32);
+		let up = Vec3::new((phi.cos() * theta.sin()) as f32, phi.sin() as f32, (phi.cos() * theta.cos()) as f32);
+
+		Quat::from_mat3(&Mat3::from_cols(east, north, up))
+	}
+}
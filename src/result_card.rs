@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+use bevy::render::view::screenshot::ScreenshotManager;
+use bevy::window::PrimaryWindow;
+use std::fmt::Write;
+
+use crate::ActionInput;
+use crate::Constellation;
+
+use crate::input::Action;
+use crate::chart::escape;
+use crate::seed::unix_time;
+
+const CARD_WIDTH: f32 = 800.0;
+const CARD_HEIGHT: f32 = 450.0;
+// The whole sky, right ascension across and declination down, under the texts
+const MAP_TOP: f32 = 190.0;
+const MAP_MARGIN: f32 = 30.0;
+
+// Made when the game is over, saved on demand
+#[derive(Resource, Default)]
+pub struct ResultCard {
+	pub svg: String,
+}
+
+// Texts from the top, the first one larger, then a map with every figure of
+// the sky culture, the ones asked in green when found and red otherwise
+pub fn card_svg(texts: &[String], figures: &[Constellation], answers: &[(String, bool)]) -> String {
+	let map_width = CARD_WIDTH - 2.0 * MAP_MARGIN;
+	let map_height = CARD_HEIGHT - MAP_TOP - MAP_MARGIN;
+	// East on the left, as the sky is seen
+	let map_point = |rah: f64, dec: f64| Vec2::new(
+		MAP_MARGIN + (1.0 - rah as f32 / 24.0) * map_width,
+		MAP_TOP + (90.0 - dec as f32) / 180.0 * map_height,
+	);
+
+	let mut svg = String::new();
+	let _ = writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif">"#, w = CARD_WIDTH, h = CARD_HEIGHT);
+	let _ = writeln!(svg, r##"<rect width="{}" height="{}" rx="16" fill="#0b1026"/>"##, CARD_WIDTH, CARD_HEIGHT);
+
+	for (index, text) in texts.iter().enumerate() {
+		let (y, size, color) = if index == 0 { (60.0, 40, "#ffffff") } else { (60.0 + 30.0 * index as f32 + 10.0, 20, "#b8c0d8") };
+		let _ = writeln!(svg, r#"<text x="{}" y="{:.0}" font-size="{}" fill="{}">{}</text>"#, MAP_MARGIN, y, size, color, escape(text));
+	}
+
+	let _ = writeln!(svg, r##"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="#2a3560"/>"##, MAP_MARGIN, MAP_TOP, map_width, map_height);
+
+	for figure in figures {
+		let (color, width) = match answers.iter().find(|(name, _)| name == &figure.name) {
+			Some((_, true)) => ("#4cd964", 2.5),
+			Some((_, false)) => ("#ff5a52", 2.5),
+			None => ("#39456e", 1.0),
+		};
+
+		for line in figure.lines.iter() {
+			let (Some(start), Some(end)) = (figure.stars.get(line[0] as usize), figure.stars.get(line[1] as usize)) else {
+				continue;
+			};
+			// Lines across the 0h edge would run across the whole map
+			if (start.rah - end.rah).abs() > 12.0 {
+				continue;
+			}
+			let (start, end) = (map_point(start.rah, start.dec), map_point(end.rah, end.dec));
+			let _ = writeln!(svg, r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{}" stroke-width="{}" stroke-linecap="round"/>"#, start.x, start.y, end.x, end.y, color, width);
+		}
+	}
+
+	let _ = writeln!(svg, "</svg>");
+	svg
+}
+
+// On the game over screen the chart key saves the card instead of a chart
+pub fn save(
+	actions: ActionInput,
+	card: Res<ResultCard>,
+) {
+	if actions.just_pressed(Action::Chart) && !card.svg.is_empty() {
+		crate::settings::export(&format!("astraea-result-{}.svg", unix_time() as u64), card.svg.as_bytes());
+	}
+}
+
+// Saved next to the game on desktop, downloaded by the browser on the web build
+pub fn screenshot(
+	actions: ActionInput,
+	window_query: Query<Entity, With<PrimaryWindow>>,
+	mut screenshot_manager: ResMut<ScreenshotManager>,
+) {
+	if !actions.just_pressed(Action::Screenshot) {
+		return;
+	}
+
+	let Ok(window) = window_query.get_single() else {
+		return;
+	};
+
+	let path = format!("astraea-{}.png", unix_time() as u64);
+	match screenshot_manager.save_screenshot_to_disk(window, &path) {
+		Ok(()) => info!("saving screenshot to {}", path),
+		Err(error) => warn!("could not take a screenshot : {}", error),
+	}
+}
@@ -0,0 +1,128 @@
+use bevy::prelude::*;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+// Crockford's base 32, without the letters that look like digits
+const CODE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const SEED_DIGITS: usize = 7;
+// The daily challenge is played with the western figures, so that everyone
+// gets the same questions whatever sky culture they had chosen
+pub const DAILY_CULTURE: usize = 0;
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum RunMode {
+	Random,
+	// Days since the unix epoch, in UTC so that everyone shares the day
+	Daily(i64),
+	Shared,
+}
+
+// Randomness of a run. ChaCha gives the same sequence on every platform and
+// rand version, so a code typed on the web replays a desktop run
+#[derive(Resource)]
+pub struct GameRng {
+	pub seed: u32,
+	pub mode: RunMode,
+	rng: ChaCha8Rng,
+}
+
+impl Default for GameRng {
+	fn default() -> Self {
+		GameRng::new(rand::random(), RunMode::Random)
+	}
+}
+
+impl GameRng {
+	pub fn new(seed: u32, mode: RunMode) -> Self {
+		GameRng {
+			seed,
+			mode,
+			rng: ChaCha8Rng::seed_from_u64(seed as u64),
+		}
+	}
+
+	pub fn random() -> Self {
+		GameRng::default()
+	}
+
+	pub fn daily() -> Self {
+		let day = (unix_time() / 86400.0).floor() as i64;
+		GameRng::new(daily_seed(day), RunMode::Daily(day))
+	}
+
+	pub fn rng(&mut self) -> &mut ChaCha8Rng {
+		&mut self.rng
+	}
+
+	// The sky culture is part of the code, the same seed gives another run with other figures
+	pub fn code(&self, culture: usize) -> String {
+		let mut code = String::new();
+		code.push(CODE_ALPHABET[culture % 32] as char);
+
+		for digit in (0..SEED_DIGITS).rev() {
+			code.push(CODE_ALPHABET[(self.seed as u64 >> (5 * digit)) as usize % 32] as char);
+		}
+
+		format!("{}-{}", &code[0..4], &code[4..])
+	}
+}
+
+// Reads a code back into its sky culture and seed, being lenient on case,
+// separators and letters mistaken for digits
+pub fn parse_code(text: &str) -> Option<(usize, u32)> {
+	let mut values = vec![];
+
+	for c in text.to_uppercase().chars() {
+		let c = match c {
+			'-' | ' ' => continue,
+			'I' | 'L' => '1',
+			'O' => '0',
+			c => c,
+		};
+		let value = CODE_ALPHABET.iter().position(|letter| *letter as char == c)?;
+		values.push(value as u64);
+	}
+
+	if values.len() != SEED_DIGITS + 1 {
+		return None;
+	}
+
+	let seed = values[1..].iter().fold(0, |seed, value| (seed << 5) | value);
+
+	Some((values[0] as usize, u32::try_from(seed).ok()?))
+}
+
+// Year, month and day of a day number, from Howard Hinnant's civil_from_days
+pub fn date(day: i64) -> (i64, u32, u32) {
+	let z = day + 719468;
+	let era = z.div_euclid(146097);
+	let day_of_era = z.rem_euclid(146097);
+	let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let month_index = (5 * day_of_year + 2) / 153;
+	let day_of_month = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+	let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+	let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+	(year, month, day_of_month)
+}
+
+// Consecutive days should not give related seeds
+fn daily_seed(day: i64) -> u32 {
+	let mut rng = ChaCha8Rng::seed_from_u64(day as u64);
+	rng.next_u32()
+}
+
+// SystemTime is not available in the browser
+#[cfg(not(target_arch = "wasm32"))]
+pub fn unix_time() -> f64 {
+	std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.map(|duration| duration.as_secs_f64())
+		.unwrap_or(0.0)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn unix_time() -> f64 {
+	crate::web::now_ms() / 1000.0
+}
@@ -0,0 +1,181 @@
+use bevy::prelude::*;
+use bevy::input::keyboard::Key;
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::ButtonState;
+
+use crate::GameState;
+use crate::SeedEntryMenu;
+use crate::Locale;
+use crate::Localized;
+use crate::InputMap;
+use crate::SkyCultures;
+use crate::GameRng;
+use crate::ActionInput;
+use crate::Settings;
+
+use crate::input::Action;
+use crate::seed::RunMode;
+use crate::seed::parse_code;
+
+#[derive(Component)]
+pub struct CodeLabel;
+
+#[derive(Component)]
+pub struct SeedHelp;
+
+#[derive(Resource)]
+pub struct SeedEntry {
+	text: String,
+	invalid: bool,
+	// The key that opened the screen is still among the pending events
+	skip_pending: bool,
+}
+
+impl Default for SeedEntry {
+	fn default() -> Self {
+		SeedEntry {
+			text: String::new(),
+			invalid: false,
+			skip_pending: true,
+		}
+	}
+}
+
+pub fn setup(
+	mut commands: Commands,
+	mut entry: ResMut<SeedEntry>,
+) {
+	*entry = SeedEntry::default();
+
+	let container_node = NodeBundle {
+		style: Style {
+			width: Val::Percent(100.0),
+			height: Val::Percent(100.0),
+			flex_direction: FlexDirection::Column,
+			justify_content: JustifyContent::Center,
+			align_items: AlignItems::Center,
+			..default()
+		},
+		..default()
+	};
+
+	let container = commands.spawn((container_node, SeedEntryMenu)).id();
+
+	let title_text_node = TextBundle::from_section(
+		"",
+		TextStyle {
+			font_size: 50.0,
+			color: Color::WHITE,
+			// font: asset_server.load("fonts/FiraSans-Bold.ttf"), // Load font if needed
+			..default()
+		},
+	).with_style(Style {
+		margin: UiRect::bottom(Val::Px(20.0)),
+		..default()
+	});
+
+	let code_text_node = TextBundle::from_section(
+		"",
+		TextStyle {
+			font_size: 40.0,
+			color: Color::WHITE,
+			// font: asset_server.load("fonts/FiraSans-Regular.ttf"), // Load font if needed
+			..default()
+		},
+	);
+
+	let help_text_node = TextBundle::from_section(
+		"",
+		TextStyle {
+			font_size: 20.0,
+			color: Color::srgb(0.4,0.4,0.4),
+			// font: asset_server.load("fonts/FiraSans-Regular.ttf"), // Load font if needed
+			..default()
+		},
+	).with_style(Style {
+		margin: UiRect::top(Val::Px(20.0)),
+		..default()
+	});
+
+	let title_text = commands.spawn((title_text_node, SeedEntryMenu, Localized::new("seed-title"))).id();
+	let code_text = commands.spawn((code_text_node, SeedEntryMenu, CodeLabel)).id();
+	let help_text = commands.spawn((help_text_node, SeedEntryMenu, SeedHelp)).id();
+
+	commands.entity(container).push_children(&[title_text, code_text, help_text]);
+}
+
+// Typed characters come from the logical key so that the layout does not matter
+pub fn player_interact(
+	mut keyboard_events: EventReader<KeyboardInput>,
+	actions: ActionInput,
+	mut entry: ResMut<SeedEntry>,
+	mut cultures: ResMut<SkyCultures>,
+	mut game_rng: ResMut<GameRng>,
+	mut game_state: ResMut<NextState<GameState>>,
+	settings: Res<Settings>,
+) {
+	if entry.skip_pending {
+		keyboard_events.clear();
+		entry.skip_pending = false;
+		return;
+	}
+
+	// Select leaves too, for a controller opening the screen from the menu
+	if actions.just_pressed(Action::Back) {
+		game_state.set(GameState::Start);
+		return;
+	}
+
+	for event in keyboard_events.read() {
+		if event.state != ButtonState::Pressed {
+			continue;
+		}
+
+		match &event.logical_key {
+			Key::Backspace => {
+				entry.text.pop();
+				entry.invalid = false;
+			}
+			Key::Enter => {
+				match parse_code(&entry.text) {
+					Some((culture, seed)) if culture < cultures.content.len() => {
+						cultures.play_on(culture);
+						*game_rng = GameRng::new(seed, RunMode::Shared);
+						// First games go through the tutorial, as from the start screen
+						game_state.set(if settings.tutorial { GameState::Tutorial } else { GameState::Game });
+					}
+					_ => entry.invalid = true,
+				}
+			}
+			Key::Character(characters) => {
+				for c in characters.chars() {
+					if c.is_ascii_alphanumeric() && entry.text.len() < 12 {
+						entry.text.push(c.to_ascii_uppercase());
+						entry.invalid = false;
+					}
+				}
+			}
+			_ => {}
+		}
+	}
+}
+
+pub fn ui_labels(
+	entry: Res<SeedEntry>,
+	input_map: Res<InputMap>,
+	locale: Res<Locale>,
+	mut code_query: Query<&mut Text, (With<CodeLabel>, Without<SeedHelp>)>,
+	mut help_query: Query<&mut Text, (With<SeedHelp>, Without<CodeLabel>)>,
+) {
+	if let Ok(mut text) = code_query.get_single_mut() {
+		text.sections[0].value = format!("{}_", entry.text);
+	}
+
+	if let Ok(mut text) = help_query.get_single_mut() {
+		text.sections[0].value = if entry.invalid {
+			locale.text("seed-invalid")
+		} else {
+			locale.text_with("seed-help", &[("key".into(), input_map.key_name(Action::Back))])
+		};
+	}
+}
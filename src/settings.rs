@@ -0,0 +1,226 @@
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::locale::Language;
+
+use crate::EASYNESS;
+
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Difficulty {
+	Easy,
+	#[default]
+	Normal,
+	Hard,
+}
+
+impl Difficulty {
+	const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+	// How much bigger stars belonging to a constellation are drawn
+	pub fn easyness(&self) -> f32 {
+		match self {
+			Difficulty::Easy => 2.0,
+			Difficulty::Normal => EASYNESS,
+			Difficulty::Hard => 1.0,
+		}
+	}
+
+	pub fn locale_key(&self) -> String {
+		format!("difficulty-{:?}", self).to_lowercase()
+	}
+
+	pub fn next(&self) -> Difficulty {
+		let index = Difficulty::ALL.iter().position(|difficulty| difficulty == self).unwrap_or(0);
+		Difficulty::ALL[(index + 1) % Difficulty::ALL.len()]
+	}
+
+	pub fn previous(&self) -> Difficulty {
+		let index = Difficulty::ALL.iter().position(|difficulty| difficulty == self).unwrap_or(0);
+		Difficulty::ALL[(index + Difficulty::ALL.len() - 1) % Difficulty::ALL.len()]
+	}
+}
+
+// Naked eye limiting magnitude at the zenith for each Bortle class,
+// from 1 (excellent dark site) to 9 (inner city)
+pub const BORTLE_LIMITS: [f32; 9] = [7.6, 7.1, 6.6, 6.1, 5.6, 5.1, 4.6, 4.1, 4.0];
+
+// Player preferences, multipliers are relative to the constants in main
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Settings {
+	pub drag_sensitivity: f32,
+	pub star_size: f32,
+	pub line_brightness: f32,
+	pub master_volume: f32,
+	pub music_volume: f32,
+	pub sfx_volume: f32,
+	pub language: Language,
+	pub difficulty: Difficulty,
+	pub zoom_to_cursor: bool,
+	pub muted: bool,
+	// Fainter stars are hidden, the default shows the whole catalog
+	pub limiting_magnitude: f32,
+	// Extinction, twinkling and horizon glow for the observer's place and time
+	pub atmosphere: bool,
+	// Constellation and bright star names drawn in the sky while exploring
+	pub labels: bool,
+	// The tutorial runs before the next game, until it is done or skipped
+	pub tutorial: bool,
+}
+
+impl Default for Settings {
+	fn default() -> Self {
+		Settings {
+			drag_sensitivity: 1.0,
+			star_size: 1.0,
+			line_brightness: 1.0,
+			master_volume: 1.0,
+			music_volume: 1.0,
+			sfx_volume: 1.0,
+			language: Language::default(),
+			difficulty: Difficulty::default(),
+			zoom_to_cursor: true,
+			muted: false,
+			limiting_magnitude: 8.5,
+			atmosphere: false,
+			labels: true,
+			tutorial: true,
+		}
+	}
+}
+
+impl Settings {
+	pub fn load() -> Self {
+		load("settings").unwrap_or_default()
+	}
+
+	pub fn save(&self) {
+		save("settings", self);
+	}
+
+	// Volumes actually played, after the master volume and mute
+	pub fn music_level(&self) -> f32 {
+		if self.muted {
+			return 0.0;
+		}
+		self.master_volume * self.music_volume
+	}
+
+	pub fn sfx_level(&self) -> f32 {
+		if self.muted {
+			return 0.0;
+		}
+		self.master_volume * self.sfx_volume
+	}
+
+	// The Bortle class is only a view on the limiting magnitude, the darkest class it reaches
+	pub fn bortle_class(&self) -> usize {
+		BORTLE_LIMITS.iter()
+			.position(|limit| self.limiting_magnitude >= *limit - 0.05)
+			.unwrap_or(BORTLE_LIMITS.len() - 1) + 1
+	}
+
+	pub fn set_bortle_class(&mut self, class: usize) {
+		self.limiting_magnitude = BORTLE_LIMITS[class.clamp(1, BORTLE_LIMITS.len()) - 1];
+	}
+
+	// 0 under the darkest sky, 1 under the brightest Bortle class
+	pub fn light_pollution(&self) -> f32 {
+		let darkest = BORTLE_LIMITS[0];
+		let brightest = BORTLE_LIMITS[BORTLE_LIMITS.len() - 1];
+		((darkest - self.limiting_magnitude) / (darkest - brightest)).clamp(0.0, 1.0)
+	}
+}
+
+// Apps given their settings, like headless ones and tests, keep everything
+// in memory rather than write over the player's preferences
+static IN_MEMORY: AtomicBool = AtomicBool::new(false);
+
+pub fn keep_in_memory() {
+	IN_MEMORY.store(true, Ordering::Relaxed);
+}
+
+// Preferences are stored as json, in the user config directory on desktop
+// and in the local storage on the web build
+pub fn load<T: DeserializeOwned>(name: &str) -> Option<T> {
+	let content = read(name)?;
+
+	match serde_json::from_str(&content) {
+		Ok(value) => Some(value),
+		Err(error) => {
+			warn!("invalid {} settings : {}", name, error);
+			None
+		}
+	}
+}
+
+pub fn save<T: Serialize>(name: &str, value: &T) {
+	if IN_MEMORY.load(Ordering::Relaxed) {
+		return;
+	}
+
+	let content = match serde_json::to_string_pretty(value) {
+		Ok(content) => content,
+		Err(error) => {
+			warn!("could not serialize {} settings : {}", name, error);
+			return;
+		}
+	};
+
+	write(name, &content);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn config_dir() -> Option<std::path::PathBuf> {
+	let base = std::env::var_os("XDG_CONFIG_HOME")
+		.map(std::path::PathBuf::from)
+		.or_else(|| std::env::var_os("APPDATA").map(std::path::PathBuf::from))
+		.or_else(|| std::env::var_os("HOME").map(|home| std::path::PathBuf::from(home).join(".config")))?;
+
+	Some(base.join("astraea"))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read(name: &str) -> Option<String> {
+	let path = config_dir()?.join(format!("{}.json", name));
+	std::fs::read_to_string(path).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write(name: &str, content: &str) {
+	let Some(dir) = config_dir() else {
+		warn!("no config directory, {} settings not saved", name);
+		return;
+	};
+
+	if let Err(error) = std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(dir.join(format!("{}.json", name)), content)) {
+		warn!("could not save {} settings : {}", name, error);
+	}
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read(name: &str) -> Option<String> {
+	crate::web::storage_get(&format!("astraea-{}", name))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write(name: &str, content: &str) {
+	crate::web::storage_set(&format!("astraea-{}", name), content);
+}
+
+// Files made for the player, like star charts : written in the current
+// directory on desktop, downloaded by the browser on the web build
+#[cfg(not(target_arch = "wasm32"))]
+pub fn export(file_name: &str, content: &[u8]) {
+	match std::fs::write(file_name, content) {
+		Ok(()) => info!("{} saved", file_name),
+		Err(error) => warn!("could not save {} : {}", file_name, error),
+	}
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn export(file_name: &str, content: &[u8]) {
+	crate::web::download(file_name, content);
+}
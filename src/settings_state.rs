@@ -0,0 +1,344 @@
+use bevy::prelude::*;
+
+use crate::GameState;
+use crate::SettingsMenu;
+use crate::Locale;
+use crate::Localized;
+use crate::InputMap;
+use crate::Settings;
+use crate::ActionInput;
+
+use crate::input::Action;
+use crate::settings::BORTLE_LIMITS;
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+enum Setting {
+	DragSensitivity,
+	StarSize,
+	LineBrightness,
+	MasterVolume,
+	MusicVolume,
+	SfxVolume,
+	Mute,
+	Language,
+	Difficulty,
+	ZoomToCursor,
+	Bortle,
+	LimitingMagnitude,
+	Atmosphere,
+	Labels,
+	Tutorial,
+}
+
+impl Setting {
+	const ALL: [Setting; 15] = [
+		Setting::DragSensitivity,
+		Setting::StarSize,
+		Setting::LineBrightness,
+		Setting::MasterVolume,
+		Setting::MusicVolume,
+		Setting::SfxVolume,
+		Setting::Mute,
+		Setting::Language,
+		Setting::Difficulty,
+		Setting::ZoomToCursor,
+		Setting::Bortle,
+		Setting::LimitingMagnitude,
+		Setting::Atmosphere,
+		Setting::Labels,
+		Setting::Tutorial,
+	];
+
+	fn locale_key(&self) -> &'static str {
+		match self {
+			Setting::DragSensitivity => "setting-drag-sensitivity",
+			Setting::StarSize => "setting-star-size",
+			Setting::LineBrightness => "setting-line-brightness",
+			Setting::MasterVolume => "setting-master-volume",
+			Setting::MusicVolume => "setting-music-volume",
+			Setting::SfxVolume => "setting-sfx-volume",
+			Setting::Mute => "setting-mute",
+			Setting::Language => "setting-language",
+			Setting::Difficulty => "setting-difficulty",
+			Setting::ZoomToCursor => "setting-zoom-to-cursor",
+			Setting::Bortle => "setting-bortle",
+			Setting::LimitingMagnitude => "setting-limiting-magnitude",
+			Setting::Atmosphere => "setting-atmosphere",
+			Setting::Labels => "setting-labels",
+			Setting::Tutorial => "setting-tutorial",
+		}
+	}
+
+	// Slider settings, with their minimum, maximum and step
+	fn range(&self) -> Option<(f32, f32, f32)> {
+		match self {
+			Setting::DragSensitivity => Some((0.25, 3.0, 0.25)),
+			Setting::StarSize => Some((0.5, 2.0, 0.1)),
+			Setting::LineBrightness => Some((0.2, 2.0, 0.2)),
+			Setting::MasterVolume | Setting::MusicVolume | Setting::SfxVolume => Some((0.0, 1.0, 0.1)),
+			Setting::LimitingMagnitude => Some((3.0, 8.5, 0.1)),
+			_ => None,
+		}
+	}
+
+	fn value(&self, settings: &Settings) -> Option<f32> {
+		match self {
+			Setting::DragSensitivity => Some(settings.drag_sensitivity),
+			Setting::StarSize => Some(settings.star_size),
+			Setting::LineBrightness => Some(settings.line_brightness),
+			Setting::MasterVolume => Some(settings.master_volume),
+			Setting::MusicVolume => Some(settings.music_volume),
+			Setting::SfxVolume => Some(settings.sfx_volume),
+			Setting::LimitingMagnitude => Some(settings.limiting_magnitude),
+			_ => None,
+		}
+	}
+
+	fn value_mut<'a>(&self, settings: &'a mut Settings) -> Option<&'a mut f32> {
+		match self {
+			Setting::DragSensitivity => Some(&mut settings.drag_sensitivity),
+			Setting::StarSize => Some(&mut settings.star_size),
+			Setting::LineBrightness => Some(&mut settings.line_brightness),
+			Setting::MasterVolume => Some(&mut settings.master_volume),
+			Setting::MusicVolume => Some(&mut settings.music_volume),
+			Setting::SfxVolume => Some(&mut settings.sfx_volume),
+			Setting::LimitingMagnitude => Some(&mut settings.limiting_magnitude),
+			_ => None,
+		}
+	}
+
+	// Taps go forward and start over past the maximum, arrows stop at the ends
+	fn change(&self, settings: &mut Settings, forward: bool, wrap: bool) {
+		match self {
+			Setting::Language => {
+				settings.language = if forward {
+					settings.language.next()
+				} else {
+					settings.language.previous()
+				};
+			}
+			Setting::Difficulty => {
+				settings.difficulty = if forward {
+					settings.difficulty.next()
+				} else {
+					settings.difficulty.previous()
+				};
+			}
+			Setting::Mute => {
+				settings.muted = !settings.muted;
+			}
+			Setting::ZoomToCursor => {
+				settings.zoom_to_cursor = !settings.zoom_to_cursor;
+			}
+			Setting::Atmosphere => {
+				settings.atmosphere = !settings.atmosphere;
+			}
+			Setting::Labels => {
+				settings.labels = !settings.labels;
+			}
+			Setting::Tutorial => {
+				settings.tutorial = !settings.tutorial;
+			}
+			Setting::Bortle => {
+				let class = settings.bortle_class();
+				let last = BORTLE_LIMITS.len();
+				let class = match (forward, wrap) {
+					(true, true) if class == last => 1,
+					(true, _) => (class + 1).min(last),
+					(false, _) => class.saturating_sub(1).max(1),
+				};
+				settings.set_bortle_class(class);
+			}
+			_ => {
+				let (Some((min, max, step)), Some(value)) = (self.range(), self.value_mut(settings)) else {
+					return;
+				};
+
+				let direction = if forward { 1.0 } else { -1.0 };
+				// Rounding to the step keeps the values from drifting
+				let mut new_value = ((*value + direction * step) / step).round() * step;
+
+				if wrap && new_value > max + step / 2.0 {
+					new_value = min;
+				}
+
+				*value = new_value.clamp(min, max);
+			}
+		}
+	}
+
+	fn value_text(&self, settings: &Settings, locale: &Locale) -> String {
+		match self {
+			Setting::Language => locale.text("language-name"),
+			Setting::Difficulty => locale.text(&settings.difficulty.locale_key()),
+			Setting::Mute => on_off(settings.muted, locale),
+			Setting::ZoomToCursor => on_off(settings.zoom_to_cursor, locale),
+			Setting::Bortle => locale.text(&format!("bortle-{}", settings.bortle_class())),
+			Setting::Atmosphere => on_off(settings.atmosphere, locale),
+			Setting::Labels => on_off(settings.labels, locale),
+			Setting::Tutorial => on_off(settings.tutorial, locale),
+			Setting::LimitingMagnitude => format!("{:.1}", settings.limiting_magnitude),
+			_ => format!("{:.0} %", self.value(settings).unwrap_or(0.0) * 100.0),
+		}
+	}
+}
+
+fn on_off(value: bool, locale: &Locale) -> String {
+	if value {
+		locale.text("setting-on")
+	} else {
+		locale.text("setting-off")
+	}
+}
+
+#[derive(Component)]
+pub struct SettingRow(usize);
+
+#[derive(Component)]
+pub struct SettingsHelp;
+
+#[derive(Resource, Default)]
+pub struct SettingsScreen {
+	selected: usize,
+}
+
+pub fn setup(
+	mut commands: Commands,
+	mut screen: ResMut<SettingsScreen>,
+) {
+	*screen = SettingsScreen::default();
+
+	let container_node = NodeBundle {
+		style: Style {
+			width: Val::Percent(100.0),
+			height: Val::Percent(100.0),
+			flex_direction: FlexDirection::Column,
+			justify_content: JustifyContent::Center,
+			align_items: AlignItems::Center,
+			..default()
+		},
+		..default()
+	};
+
+	let container = commands.spawn((container_node, SettingsMenu)).id();
+
+	let title_text_node = TextBundle::from_section(
+		"",
+		TextStyle {
+			font_size: 50.0,
+			color: Color::WHITE,
+			// font: asset_server.load("fonts/FiraSans-Bold.ttf"), // Load font if needed
+			..default()
+		},
+	).with_style(Style {
+		margin: UiRect::bottom(Val::Px(20.0)),
+		..default()
+	});
+
+	let title_text = commands.spawn((title_text_node, SettingsMenu, Localized::new("settings-title"))).id();
+	commands.entity(container).push_children(&[title_text]);
+
+	for index in 0..Setting::ALL.len() {
+		let row_text_node = TextBundle::from_section(
+			"",
+			TextStyle {
+				font_size: 25.0,
+				color: Color::WHITE,
+				// font: asset_server.load("fonts/FiraSans-Regular.ttf"), // Load font if needed
+				..default()
+			},
+		);
+
+		let row_text = commands.spawn((row_text_node, SettingsMenu, SettingRow(index), Interaction::default())).id();
+		commands.entity(container).push_children(&[row_text]);
+	}
+
+	let help_text_node = TextBundle::from_section(
+		"",
+		TextStyle {
+			font_size: 20.0,
+			color: Color::srgb(0.4,0.4,0.4),
+			// font: asset_server.load("fonts/FiraSans-Regular.ttf"), // Load font if needed
+			..default()
+		},
+	).with_style(Style {
+		margin: UiRect::top(Val::Px(20.0)),
+		..default()
+	});
+
+	let help_text = commands.spawn((help_text_node, SettingsMenu, SettingsHelp)).id();
+	commands.entity(container).push_children(&[help_text]);
+}
+
+pub fn player_interact(
+	actions: ActionInput,
+	keys: Res<ButtonInput<KeyCode>>,
+	gamepads: Res<Gamepads>,
+	gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+	mut settings: ResMut<Settings>,
+	mut screen: ResMut<SettingsScreen>,
+	mut game_state: ResMut<NextState<GameState>>,
+	row_query: Query<(&Interaction, &SettingRow), Changed<Interaction>>,
+) {
+	if actions.just_pressed(Action::Back) {
+		game_state.set(GameState::Start);
+		return;
+	}
+
+	let just_pressed = |key: KeyCode, button_type: GamepadButtonType| {
+		keys.just_pressed(key)
+			|| gamepads.iter().any(|gamepad| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button_type)))
+	};
+
+	if just_pressed(KeyCode::ArrowDown, GamepadButtonType::DPadDown) {
+		screen.selected = (screen.selected + 1) % Setting::ALL.len();
+	}
+
+	if just_pressed(KeyCode::ArrowUp, GamepadButtonType::DPadUp) {
+		screen.selected = (screen.selected + Setting::ALL.len() - 1) % Setting::ALL.len();
+	}
+
+	let setting = Setting::ALL[screen.selected];
+
+	if just_pressed(KeyCode::ArrowRight, GamepadButtonType::DPadRight) {
+		setting.change(&mut settings, true, false);
+		settings.save();
+	}
+
+	if just_pressed(KeyCode::ArrowLeft, GamepadButtonType::DPadLeft) {
+		setting.change(&mut settings, false, false);
+		settings.save();
+	}
+
+	for (interaction, row) in row_query.iter() {
+		if *interaction == Interaction::Pressed {
+			screen.selected = row.0;
+			Setting::ALL[row.0].change(&mut settings, true, true);
+			settings.save();
+		}
+	}
+}
+
+pub fn ui_labels(
+	screen: Res<SettingsScreen>,
+	settings: Res<Settings>,
+	input_map: Res<InputMap>,
+	locale: Res<Locale>,
+	mut row_query: Query<(&mut Text, &SettingRow), Without<SettingsHelp>>,
+	mut help_query: Query<&mut Text, (With<SettingsHelp>, Without<SettingRow>)>,
+) {
+	for (mut text, row) in row_query.iter_mut() {
+		let setting = Setting::ALL[row.0];
+
+		text.sections[0].value = format!("{} : {}", locale.text(setting.locale_key()), setting.value_text(&settings, &locale));
+		text.sections[0].style.color = if row.0 == screen.selected {
+			Color::WHITE
+		} else {
+			Color::srgb(0.4,0.4,0.4)
+		};
+	}
+
+	if let Ok(mut text) = help_query.get_single_mut() {
+		text.sections[0].value = locale.text_with("settings-help", &[("key".into(), input_map.key_name(Action::Back))]);
+	}
+}
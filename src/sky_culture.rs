@@ -0,0 +1,194 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::Sky;
+use crate::Constellation;
+use crate::StarPos;
+
+use crate::celestial_to_cartesian;
+use crate::load_star_catalog;
+use crate::star_coordinates;
+
+// Figures a sky culture needs for the four answers of each question
+const MIN_FIGURES: usize = 4;
+
+// Figure files follow the Stellarium sky culture index, with HR numbers
+// from the Yale catalog in place of Hipparcos numbers
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct CultureData {
+	id: String,
+	name: String,
+	description: String,
+	constellations: Vec<FigureData>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct FigureData {
+	id: String,
+	common_name: CommonName,
+	lines: Vec<Vec<usize>>,
+	description: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct CommonName {
+	english: String,
+	native: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct SkyCulture {
+	pub name: String,
+	pub description: String,
+	pub(crate) constellations: Vec<Constellation>,
+}
+
+#[derive(Resource, Default)]
+pub struct SkyCultures {
+	pub content: Vec<SkyCulture>,
+	pub selected: usize,
+	// The player's own choice while a run is played on other figures
+	kept: Option<usize>,
+}
+
+impl SkyCultures {
+	pub fn current(&self) -> &SkyCulture {
+		&self.content[self.selected]
+	}
+
+	pub fn select_next(&mut self) {
+		self.selected = (self.selected + 1) % self.content.len();
+	}
+
+	// For a single run, the player's choice comes back on the start screen
+	pub fn play_on(&mut self, culture: usize) {
+		self.kept.get_or_insert(self.selected);
+		self.selected = culture;
+	}
+}
+
+pub fn setup(mut cultures: ResMut<SkyCultures>) {
+	let western : Vec<Constellation> = serde_json::from_str(include_str!("../data/constellations.json")).expect("no constellation json provided");
+
+	cultures.content = vec![SkyCulture {
+		name: "Western".into(),
+		description: "The 88 modern constellations of the IAU".into(),
+		constellations: western,
+	}];
+
+	let culture_files = [
+		include_str!("../data/skycultures/chinese.json"),
+		include_str!("../data/skycultures/norse.json"),
+		include_str!("../data/skycultures/polynesian.json"),
+	];
+
+	let mut catalog : HashMap<usize, (f64, f64)> = HashMap::new();
+
+	for star in load_star_catalog() {
+		let Ok(hr) = star.hr.parse::<usize>() else {
+			continue;
+		};
+		catalog.insert(hr, star_coordinates(star));
+	}
+
+	for culture_file in culture_files {
+		let culture_data : CultureData = serde_json::from_str(culture_file).expect("invalid sky culture json");
+		let culture = build_culture(culture_data, &catalog);
+
+		// Each question shows four answers
+		if culture.constellations.len() < MIN_FIGURES {
+			warn!("{} : only {} figures, at least {} are needed", culture.name, culture.constellations.len(), MIN_FIGURES);
+			continue;
+		}
+		cultures.content.push(culture);
+	}
+}
+
+pub fn restore_selection(mut cultures: ResMut<SkyCultures>) {
+	// Read first, so that the figures are only rebuilt when they change
+	let Some(kept) = cultures.kept else {
+		return;
+	};
+	cultures.selected = kept;
+	cultures.kept = None;
+}
+
+pub fn apply_selection(
+	cultures: Res<SkyCultures>,
+	mut sky: ResMut<Sky>,
+) {
+	if cultures.content.is_empty() {
+		return;
+	}
+	sky.content = cultures.current().constellations.clone();
+}
+
+fn build_culture(culture_data: CultureData, catalog: &HashMap<usize, (f64, f64)>) -> SkyCulture {
+	let mut constellations : Vec<Constellation> = vec![];
+
+	for figure in culture_data.constellations {
+		let mut stars : Vec<StarPos> = vec![];
+		let mut lines : Vec<[u32; 2]> = vec![];
+		let mut mean_pos = Vec3::ZERO;
+
+		for polyline in figure.lines {
+			let mut previous : Option<u32> = None;
+
+			for hr in polyline {
+				let Some(&(rah, dec)) = catalog.get(&hr) else {
+					warn!("{} : unknown star HR {}", figure.id, hr);
+					previous = None;
+					continue;
+				};
+
+				let index = match stars.iter().position(|star| star.bfid == format!("HR {}", hr)) {
+					Some(index) => index,
+					None => {
+						stars.push(StarPos {
+							id: stars.len(),
+							bfid: format!("HR {}", hr),
+							rah,
+							dec,
+						});
+						mean_pos += celestial_to_cartesian(rah, dec);
+						stars.len() - 1
+					}
+				} as u32;
+
+				if let Some(previous_index) = previous {
+					lines.push([previous_index, index]);
+				}
+				previous = Some(index);
+			}
+		}
+
+		if stars.is_empty() {
+			continue;
+		}
+
+		let (rah, dec) = cartesian_to_celestial(mean_pos.normalize());
+
+		constellations.push(Constellation {
+			name: figure.common_name.english,
+			rah,
+			dec,
+			stars,
+			lines,
+			description: figure.description,
+		});
+	}
+
+	SkyCulture {
+		name: culture_data.name,
+		description: culture_data.description,
+		constellations,
+	}
+}
+
+fn cartesian_to_celestial(pos: Vec3) -> (f64, f64) {
+	let rah = (pos.x as f64).atan2(pos.z as f64).to_degrees().rem_euclid(360.0) / 15.0;
+	let dec = (pos.y as f64).clamp(-1.0, 1.0).asin().to_degrees();
+
+	(rah, dec)
+}
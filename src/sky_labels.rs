@@ -0,0 +1,161 @@
+use bevy::prelude::*;
+
+use crate::Player;
+use crate::ConstellationModel;
+use crate::MainGame;
+use crate::Locale;
+use crate::ActionInput;
+use crate::Settings;
+
+use crate::input::Action;
+use crate::observer::Observer;
+
+use crate::atmosphere;
+
+use crate::celestial_to_cartesian;
+use crate::star_catalog;
+
+use crate::SKY_RADIUS;
+use crate::MAX_FOV;
+
+// Star names shown when zoomed all the way out, one magnitude more each time the field of view is halved
+const STAR_LABEL_MAGNITUDE: f32 = 1.5;
+const STAR_LABEL_MAGNITUDE_PER_ZOOM: f32 = 1.0;
+// Constellation names are placed before any star name
+const CONSTELLATION_PRIORITY: f32 = -10.0;
+// Pixels between a star and its name, and kept free around every label
+const STAR_LABEL_OFFSET: f32 = 6.0;
+const LABEL_MARGIN: f32 = 4.0;
+
+#[derive(Component)]
+pub struct SkyLabel {
+	anchor: Vec3,
+	// Magnitude for stars, labels are placed from the lowest
+	priority: f32,
+	// Name to translate again when the language changes
+	constellation: Option<String>,
+}
+
+fn spawn_label(commands: &mut Commands, text: String, font_size: f32, color: Color, label: SkyLabel) {
+	let mut label_node = TextBundle::from_section(
+		text,
+		TextStyle {
+			font_size,
+			color,
+			..default()
+		},
+	).with_style(Style {
+		position_type: PositionType::Absolute,
+		..default()
+	});
+	label_node.visibility = Visibility::Hidden;
+
+	commands.spawn((label_node, label, MainGame));
+}
+
+pub fn setup(
+	mut commands: Commands,
+	locale: Res<Locale>,
+	constellation_query: Query<&ConstellationModel>,
+	mut star_names: Local<Vec<(String, Vec3, f32)>>,
+) {
+	if star_names.is_empty() {
+		let catalog = star_catalog::load_catalog();
+		let names = star_catalog::star_names(&catalog);
+		*star_names = catalog.into_iter().zip(names)
+			.filter_map(|(star, name)| Some((name?, celestial_to_cartesian(star.rah as f64, star.dec as f64) * SKY_RADIUS, star.magnitude)))
+			.collect();
+	}
+
+	for constellation in constellation_query.iter() {
+		spawn_label(&mut commands, locale.constellation(&constellation.name), 18.0, Color::srgb(0.55, 0.65, 0.85), SkyLabel {
+			anchor: constellation.center,
+			priority: CONSTELLATION_PRIORITY,
+			constellation: Some(constellation.name.clone()),
+		});
+	}
+
+	for (name, anchor, magnitude) in star_names.iter() {
+		spawn_label(&mut commands, name.clone(), 14.0, Color::srgb(0.7, 0.7, 0.6), SkyLabel {
+			anchor: *anchor,
+			priority: *magnitude,
+			constellation: None,
+		});
+	}
+}
+
+pub fn toggle(
+	actions: ActionInput,
+	mut settings: ResMut<Settings>,
+) {
+	if actions.just_pressed(Action::Labels) {
+		settings.labels = !settings.labels;
+		settings.save();
+		info!("sky labels : {}", settings.labels);
+	}
+}
+
+// Labels follow their anchor on screen, the ones overlapping a label placed before them are hidden
+pub fn place_labels(
+	settings: Res<Settings>,
+	observer: Res<Observer>,
+	locale: Res<Locale>,
+	camera_query: Query<(&Camera, &GlobalTransform, &Projection), With<Player>>,
+	mut label_query: Query<(&SkyLabel, &Node, &mut Style, &mut Visibility, &mut Text)>,
+) {
+	let Ok((camera, camera_transform, projection)) = camera_query.get_single() else {
+		return;
+	};
+
+	let fov = match projection {
+		Projection::Perspective(perspective) => perspective.fov,
+		_ => MAX_FOV,
+	};
+	let faintest = (STAR_LABEL_MAGNITUDE + STAR_LABEL_MAGNITUDE_PER_ZOOM * (MAX_FOV / fov).log2())
+		.min(settings.limiting_magnitude);
+	let zenith = atmosphere::zenith(&settings, &observer);
+
+	let mut labels : Vec<_> = label_query.iter_mut().collect();
+	labels.sort_by(|a, b| a.0.priority.total_cmp(&b.0.priority));
+
+	let mut placed : Vec<Rect> = vec![];
+
+	for (label, node, mut style, mut visibility, mut text) in labels {
+		if locale.is_changed() {
+			if let Some(name) = &label.constellation {
+				text.sections[0].value = locale.constellation(name);
+			}
+		}
+
+		let shown = settings.labels
+			&& label.priority <= faintest
+			&& zenith.is_none_or(|zenith| label.anchor.dot(zenith) > 0.0);
+
+		let Some(position) = shown.then(|| camera.world_to_viewport(camera_transform, label.anchor)).flatten() else {
+			visibility.set_if_neq(Visibility::Hidden);
+			continue;
+		};
+
+		// Star names sit right of the star, constellation names are centred on the figure
+		let size = node.size();
+		let top_left = if label.constellation.is_some() {
+			position - size / 2.0
+		} else {
+			position + Vec2::new(STAR_LABEL_OFFSET, -size.y / 2.0)
+		};
+
+		let rect = Rect::from_corners(top_left, top_left + size).inflate(LABEL_MARGIN);
+		if placed.iter().any(|other| !other.intersect(rect).is_empty()) {
+			visibility.set_if_neq(Visibility::Hidden);
+			continue;
+		}
+		placed.push(rect);
+
+		// Only moved labels are laid out again
+		if style.left != Val::Px(top_left.x) || style.top != Val::Px(top_left.y) {
+			style.left = Val::Px(top_left.x);
+			style.top = Val::Px(top_left.y);
+		}
+		visibility.set_if_neq(Visibility::Inherited);
+	}
+}
@@ -0,0 +1,270 @@
+use bevy::prelude::*;
+
+use crate::StarData;
+use crate::Constellation;
+
+use crate::catalog::parse_ra;
+use crate::catalog::parse_dec;
+use crate::starfield::color_index;
+
+// Binary catalog, little endian : the magic, a format version and the star
+// count, then one fixed size record per star, sorted brightest first
+//   f32 right ascension in hours, f32 declination in degrees,
+//   f32 visual magnitude, f32 B-V colour index (NaN when unknown),
+//   u64 id in the source catalog, u32 flags (1 : star of a constellation)
+const MAGIC: &[u8; 4] = b"ASTR";
+const VERSION: u32 = 1;
+const HEADER_SIZE: usize = 12;
+const RECORD_SIZE: usize = 28;
+
+const FLAG_IN_CONSTELLATION: u32 = 1;
+
+// Figure stars in constellations.json are rounded, and only bright stars are drawn in figures
+const FIGURE_TOLERANCE: f32 = 0.05;
+const FIGURE_MAGNITUDE: f32 = 7.0;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CatalogStar {
+	pub id: u64,
+	pub rah: f32,
+	pub dec: f32,
+	pub magnitude: f32,
+	pub color_index: f32,
+	pub in_constellation: bool,
+}
+
+// The Yale catalog shipped with the game, with its HR numbers as ids
+pub fn parse_json_catalog(json: &str) -> Result<Vec<CatalogStar>, String> {
+	let stars : Vec<StarData> = serde_json::from_str(json).map_err(|error| error.to_string())?;
+
+	stars.into_iter().map(|star| {
+		let unreadable = |field: &str| format!("HR {} : unreadable {}", star.hr, field);
+
+		Ok(CatalogStar {
+			id: star.hr.parse().map_err(|_| unreadable("HR number"))?,
+			rah: parse_ra(&star.ra).ok_or_else(|| unreadable("right ascension"))? as f32,
+			dec: parse_dec(&star.dec).ok_or_else(|| unreadable("declination"))? as f32,
+			magnitude: star.v.trim().parse().map_err(|_| unreadable("magnitude"))?,
+			color_index: star.k.as_deref()
+				.and_then(|k| k.trim().parse::<f32>().ok())
+				.map(color_index)
+				.unwrap_or(f32::NAN),
+			in_constellation: star.constellation.is_some(),
+		})
+	}).collect()
+}
+
+// Csv exports, from VizieR or the Gaia archive for instance : a header line
+// naming the columns, separated by commas, semicolons or pipes. Right
+// ascension and declination are in degrees ("rah" for hours), rows missing
+// one of them or the magnitude are skipped
+pub fn parse_csv_catalog(text: &str, figures: &[Constellation]) -> Result<(Vec<CatalogStar>, usize), String> {
+	let mut lines = text.lines().filter(|line| !line.trim().is_empty() && !line.starts_with('#'));
+	let header = lines.next().ok_or("empty csv")?;
+
+	let separator = ['|', ';', ',', '\t'].into_iter().find(|separator| header.contains(*separator)).unwrap_or(',');
+	let columns : Vec<String> = header.split(separator).map(|column| column.trim().to_lowercase()).collect();
+	let column = |names: &[&str]| columns.iter().position(|column| names.contains(&column.as_str()));
+
+	let ra_degrees = column(&["ra", "radeg", "ra_deg", "ra_icrs", "_raj2000"]);
+	let ra_hours = column(&["rah", "ra_hours"]);
+	let dec = column(&["dec", "de", "dedeg", "dec_deg", "de_icrs", "_dej2000"]).ok_or("no declination column")?;
+	let magnitude = column(&["mag", "vmag", "v", "hpmag", "phot_g_mean_mag"]).ok_or("no magnitude column")?;
+	let color = column(&["bv", "b-v", "b_v"]);
+	let id = column(&["id", "hip", "hr", "source_id"]);
+
+	if ra_degrees.is_none() && ra_hours.is_none() {
+		return Err("no right ascension column".into());
+	}
+
+	let figure_stars : Vec<Vec3> = figures.iter()
+		.flat_map(|figure| figure.stars.iter())
+		.map(|star| crate::celestial_to_cartesian(star.rah, star.dec))
+		.collect();
+	let figure_cos = FIGURE_TOLERANCE.to_radians().cos();
+
+	let mut stars = vec![];
+	let mut skipped = 0;
+
+	for (row, line) in lines.enumerate() {
+		let fields : Vec<&str> = line.split(separator).map(str::trim).collect();
+		let number = |index: Option<usize>| index.and_then(|index| fields.get(index)).and_then(|field| field.parse::<f64>().ok());
+
+		let rah = number(ra_hours).or_else(|| number(ra_degrees).map(|degrees| degrees / 15.0));
+		let (Some(rah), Some(dec), Some(magnitude)) = (rah, number(Some(dec)), number(Some(magnitude))) else {
+			skipped += 1;
+			continue;
+		};
+
+		let rah = rah.rem_euclid(24.0);
+		if !(-90.0..=90.0).contains(&dec) {
+			skipped += 1;
+			continue;
+		}
+
+		// Gaia source ids go past what a f64 holds exactly
+		let source_id = id.and_then(|index| fields.get(index)).and_then(|field| field.parse::<u64>().ok());
+
+		let position = crate::celestial_to_cartesian(rah, dec);
+		let in_constellation = magnitude < FIGURE_MAGNITUDE as f64
+			&& figure_stars.iter().any(|figure_star| figure_star.dot(position) > figure_cos);
+
+		stars.push(CatalogStar {
+			id: source_id.unwrap_or(row as u64),
+			rah: rah as f32,
+			dec: dec as f32,
+			magnitude: magnitude as f32,
+			color_index: number(color).map(|color| color as f32).unwrap_or(f32::NAN),
+			in_constellation,
+		});
+	}
+
+	Ok((stars, skipped))
+}
+
+pub fn encode_catalog(stars: &[CatalogStar]) -> Vec<u8> {
+	let mut sorted = stars.to_vec();
+	sorted.sort_by(|a, b| a.magnitude.total_cmp(&b.magnitude));
+
+	let mut bytes = Vec::with_capacity(HEADER_SIZE + RECORD_SIZE * sorted.len());
+	bytes.extend(MAGIC);
+	bytes.extend(VERSION.to_le_bytes());
+	bytes.extend((sorted.len() as u32).to_le_bytes());
+
+	for star in sorted {
+		bytes.extend(star.rah.to_le_bytes());
+		bytes.extend(star.dec.to_le_bytes());
+		bytes.extend(star.magnitude.to_le_bytes());
+		bytes.extend(star.color_index.to_le_bytes());
+		bytes.extend(star.id.to_le_bytes());
+		let flags = if star.in_constellation { FLAG_IN_CONSTELLATION } else { 0 };
+		bytes.extend(flags.to_le_bytes());
+	}
+
+	bytes
+}
+
+pub fn decode_catalog(bytes: &[u8]) -> Result<Vec<CatalogStar>, String> {
+	if bytes.len() < HEADER_SIZE || &bytes[0..4] != MAGIC {
+		return Err("not a star catalog".into());
+	}
+
+	let word = |offset: usize| u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+	let float = |offset: usize| f32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+
+	let version = word(4);
+	if version != VERSION {
+		return Err(format!("unsupported catalog version {}", version));
+	}
+
+	// The count comes from the file, its size can overflow a 32 bit usize on the web
+	let count = word(8) as usize;
+	let Some(size) = count.checked_mul(RECORD_SIZE).and_then(|size| size.checked_add(HEADER_SIZE)) else {
+		return Err(format!("too many stars : {}", count));
+	};
+	if bytes.len() != size {
+		return Err(format!("{} bytes for {} stars", bytes.len(), count));
+	}
+
+	Ok((0..count).map(|index| {
+		let record = HEADER_SIZE + index * RECORD_SIZE;
+		CatalogStar {
+			rah: float(record),
+			dec: float(record + 4),
+			magnitude: float(record + 8),
+			color_index: float(record + 12),
+			id: u64::from_le_bytes(bytes[record + 16..record + 24].try_into().unwrap()),
+			in_constellation: word(record + 24) & FLAG_IN_CONSTELLATION != 0,
+		}
+	}).collect())
+}
+
+// Reads json, csv or binary catalogs and writes the binary one, returning
+// the number of stars written and of csv rows skipped
+pub fn convert_catalog(input: &str, output: &str, faintest: Option<f32>) -> Result<(usize, usize), String> {
+	let (mut stars, skipped) = if input.ends_with(".json") {
+		(parse_json_catalog(&read_text(input)?)?, 0)
+	} else if input.ends_with(".bin") {
+		(decode_catalog(&std::fs::read(input).map_err(|error| format!("{} : {}", input, error))?)?, 0)
+	} else {
+		let figures : Vec<Constellation> = serde_json::from_str(include_str!("../data/constellations.json")).map_err(|error| error.to_string())?;
+		parse_csv_catalog(&read_text(input)?, &figures)?
+	};
+
+	if let Some(faintest) = faintest {
+		stars.retain(|star| star.magnitude <= faintest);
+	}
+
+	std::fs::write(output, encode_catalog(&stars)).map_err(|error| format!("{} : {}", output, error))?;
+	Ok((stars.len(), skipped))
+}
+
+// The proper names of the Yale catalog, given to the stars of the loaded
+// catalog found at the same place since ids differ from one catalog to another
+pub fn star_names(stars: &[CatalogStar]) -> Vec<Option<String>> {
+	let mut names = vec![None; stars.len()];
+	let name_cos = FIGURE_TOLERANCE.to_radians().cos();
+
+	let bright : Vec<(usize, Vec3)> = stars.iter().enumerate()
+		.filter(|(_, star)| star.magnitude < FIGURE_MAGNITUDE)
+		.map(|(index, star)| (index, crate::celestial_to_cartesian(star.rah as f64, star.dec as f64)))
+		.collect();
+
+	for star in crate::load_star_catalog() {
+		let (Some(name), Some(rah), Some(dec)) = (star.name.clone(), parse_ra(&star.ra), parse_dec(&star.dec)) else {
+			continue;
+		};
+		let position = crate::celestial_to_cartesian(rah, dec);
+
+		let nearest = bright.iter()
+			.map(|(index, direction)| (*index, direction.dot(position)))
+			.filter(|(_, cos)| *cos > name_cos)
+			.max_by(|a, b| a.1.total_cmp(&b.1));
+		if let Some((index, _)) = nearest {
+			names[index] = Some(name);
+		}
+	}
+
+	names
+}
+
+fn read_text(path: &str) -> Result<String, String> {
+	std::fs::read_to_string(path).map_err(|error| format!("{} : {}", path, error))
+}
+
+// The embedded Yale catalog, unless another one is given on the command line
+pub fn load_catalog() -> Vec<CatalogStar> {
+	if let Some(stars) = load_catalog_file() {
+		return stars;
+	}
+	parse_json_catalog(include_str!("../data/stars.json")).expect("invalid star json, run astraea-data")
+}
+
+// --catalog <file> or the ASTRAEA_CATALOG variable, binary or json
+#[cfg(not(target_arch = "wasm32"))]
+fn load_catalog_file() -> Option<Vec<CatalogStar>> {
+	let mut args = std::env::args().skip_while(|arg| arg != "--catalog").skip(1);
+	let path = args.next().or_else(|| std::env::var("ASTRAEA_CATALOG").ok())?;
+
+	let stars = if path.ends_with(".json") {
+		read_text(&path).and_then(|json| parse_json_catalog(&json))
+	} else {
+		std::fs::read(&path).map_err(|error| error.to_string()).and_then(|bytes| decode_catalog(&bytes))
+	};
+
+	match stars {
+		Ok(stars) => {
+			info!("{} stars loaded from {}", stars.len(), path);
+			Some(stars)
+		}
+		Err(error) => {
+			warn!("could not load star catalog {} : {}", path, error);
+			None
+		}
+	}
+}
+
+#[cfg(target_arch = "wasm32")]
+fn load_catalog_file() -> Option<Vec<CatalogStar>> {
+	None
+}
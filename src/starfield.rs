@@ -0,0 +1,208 @@
+use bevy::prelude::*;
+use bevy::pbr::MaterialPipeline;
+use bevy::pbr::MaterialPipelineKey;
+use bevy::render::mesh::Indices;
+use bevy::render::mesh::MeshVertexAttribute;
+use bevy::render::mesh::MeshVertexBufferLayoutRef;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::AsBindGroup;
+use bevy::render::render_resource::PrimitiveTopology;
+use bevy::render::render_resource::RenderPipelineDescriptor;
+use bevy::render::render_resource::ShaderRef;
+use bevy::render::render_resource::SpecializedMeshPipelineError;
+use bevy::render::render_resource::VertexFormat;
+
+use crate::Settings;
+
+use crate::atmosphere;
+use crate::observer::Observer;
+
+pub const STAR_SHADER: Handle<Shader> = Handle::weak_from_u128(0x5f3c_2a91_7d4e_4b8a_9c61_e0b2_f7a3_d518);
+
+// Magnitude, and 1 for the stars of a constellation which are drawn bigger
+pub const ATTRIBUTE_STAR: MeshVertexAttribute = MeshVertexAttribute::new("Star", 713_902_455, VertexFormat::Float32x2);
+// Colour of the star from its B-V index, brightest channel at 1
+pub const ATTRIBUTE_STAR_COLOR: MeshVertexAttribute = MeshVertexAttribute::new("StarColor", 713_902_456, VertexFormat::Float32x3);
+
+// Radius in pixels under which stars are dimmed rather than shrunk
+const MIN_STAR_RADIUS: f32 = 0.8;
+// Stars look paler to the eye than their black body colour
+const STAR_SATURATION: f32 = 0.5;
+
+// Everything the shader needs to size and colour the stars, same layout as in stars.wgsl.
+// The ShaderType derive leaves unused size checks behind with recent compilers
+#[allow(dead_code)]
+mod params {
+	use bevy::prelude::*;
+	use bevy::render::render_resource::ShaderType;
+
+	use crate::Settings;
+
+	use crate::atmosphere;
+
+	use crate::MAX_STAR_SIZE;
+	use crate::STAR_SCALE;
+	use crate::STAR_FADE_RANGE;
+
+	use super::MIN_STAR_RADIUS;
+
+	#[derive(ShaderType, Clone, Copy, Default, Debug)]
+	pub struct StarParams {
+		zenith: Vec3,
+		atmosphere: f32,
+		channel_extinction: Vec3,
+		scale: f32,
+		max_size: f32,
+		easyness: f32,
+		limiting_magnitude: f32,
+		fade_range: f32,
+		extinction: f32,
+		twinkle_amplitude: f32,
+		max_twinkle_airmass: f32,
+		time: f32,
+		min_radius: f32,
+	}
+
+	impl StarParams {
+		pub fn new(settings: &Settings, zenith: Option<Vec3>, time: f32) -> Self {
+			StarParams {
+				zenith: zenith.unwrap_or(Vec3::Y),
+				atmosphere: if zenith.is_some() { 1.0 } else { 0.0 },
+				channel_extinction: Vec3::from(atmosphere::CHANNEL_EXTINCTION),
+				scale: STAR_SCALE * settings.star_size,
+				max_size: MAX_STAR_SIZE,
+				easyness: settings.difficulty.easyness(),
+				limiting_magnitude: settings.limiting_magnitude,
+				fade_range: STAR_FADE_RANGE,
+				extinction: atmosphere::EXTINCTION,
+				twinkle_amplitude: atmosphere::TWINKLE_AMPLITUDE,
+				max_twinkle_airmass: atmosphere::MAX_TWINKLE_AIRMASS,
+				time,
+				min_radius: MIN_STAR_RADIUS,
+			}
+		}
+	}
+}
+
+pub use params::StarParams;
+
+#[derive(Asset, TypePath, AsBindGroup, Clone, Debug)]
+pub struct StarMaterial {
+	#[uniform(0)]
+	pub params: StarParams,
+}
+
+impl Material for StarMaterial {
+	fn vertex_shader() -> ShaderRef {
+		STAR_SHADER.into()
+	}
+
+	fn fragment_shader() -> ShaderRef {
+		STAR_SHADER.into()
+	}
+
+	fn alpha_mode(&self) -> AlphaMode {
+		AlphaMode::Add
+	}
+
+	fn specialize(
+		_pipeline: &MaterialPipeline<Self>,
+		descriptor: &mut RenderPipelineDescriptor,
+		layout: &MeshVertexBufferLayoutRef,
+		_key: MaterialPipelineKey<Self>,
+	) -> Result<(), SpecializedMeshPipelineError> {
+		let vertex_layout = layout.0.get_layout(&[
+			Mesh::ATTRIBUTE_POSITION.at_shader_location(0),
+			Mesh::ATTRIBUTE_UV_0.at_shader_location(1),
+			ATTRIBUTE_STAR.at_shader_location(2),
+			ATTRIBUTE_STAR_COLOR.at_shader_location(3),
+		])?;
+		descriptor.vertex.buffers = vec![vertex_layout];
+		descriptor.primitive.cull_mode = None;
+		Ok(())
+	}
+}
+
+// The one star field, whose material follows the settings and the sky
+#[derive(Resource)]
+pub struct StarField {
+	pub material: Handle<StarMaterial>,
+}
+
+// B-V colour index of a temperature, inverting Ballesteros' formula
+pub fn color_index(temperature: f32) -> f32 {
+	let t = temperature / 4600.0;
+	let b = 2.32 * t - 2.0;
+	let c = 1.054 * t - 2.32;
+	(-b + (b * b - 4.0 * t * c).sqrt()) / (2.0 * t) / 0.92
+}
+
+// Temperature from Ballesteros' formula, then Tanner Helland's fit of the
+// black body colours. Stars without a colour index are white
+pub fn star_color(color_index: f32) -> [f32; 3] {
+	if !color_index.is_finite() {
+		return [1.0; 3];
+	}
+
+	let bv = color_index.clamp(-0.4, 2.0);
+	let temperature = 4600.0 * (1.0 / (0.92 * bv + 1.7) + 1.0 / (0.92 * bv + 0.62));
+	let t = temperature / 100.0;
+
+	let red = if t <= 66.0 { 255.0 } else { 329.698_73 * (t - 60.0).powf(-0.133_204_76) };
+	let green = if t <= 66.0 { 99.470_8 * t.ln() - 161.119_57 } else { 288.122_17 * (t - 60.0).powf(-0.075_514_85) };
+	let blue = if t >= 66.0 { 255.0 } else if t <= 19.0 { 0.0 } else { 138.517_73 * (t - 10.0).ln() - 305.044_8 };
+
+	let color = Vec3::new(red, green, blue).clamp(Vec3::ZERO, Vec3::splat(255.0));
+	let color = Vec3::ONE.lerp(color / color.max_element(), STAR_SATURATION);
+	(color / color.max_element()).to_array()
+}
+
+// One quad per star, all four corners at the star, the shader spreads them.
+// Stars are given by position, magnitude, whether they belong to a figure and B-V index
+pub fn star_mesh(stars: &[(Vec3, f32, bool, f32)]) -> Mesh {
+	let corners = [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]];
+
+	let mut positions : Vec<[f32; 3]> = Vec::with_capacity(stars.len() * 4);
+	let mut uvs : Vec<[f32; 2]> = Vec::with_capacity(stars.len() * 4);
+	let mut star_data : Vec<[f32; 2]> = Vec::with_capacity(stars.len() * 4);
+	let mut colors : Vec<[f32; 3]> = Vec::with_capacity(stars.len() * 4);
+	let mut indices : Vec<u32> = Vec::with_capacity(stars.len() * 6);
+
+	for (index, (position, magnitude, in_constellation, color_index)) in stars.iter().enumerate() {
+		let first = index as u32 * 4;
+		let color = star_color(*color_index);
+		for corner in corners {
+			positions.push(position.to_array());
+			uvs.push(corner);
+			star_data.push([*magnitude, if *in_constellation { 1.0 } else { 0.0 }]);
+			colors.push(color);
+		}
+		indices.extend([first, first + 1, first + 2, first, first + 2, first + 3]);
+	}
+
+	Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::RENDER_WORLD)
+		.with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+		.with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+		.with_inserted_attribute(ATTRIBUTE_STAR, star_data)
+		.with_inserted_attribute(ATTRIBUTE_STAR_COLOR, colors)
+		.with_inserted_indices(Indices::U32(indices))
+}
+
+// Twinkling needs the time every frame, otherwise only settings changes matter
+pub fn update_params(
+	time: Res<Time>,
+	settings: Res<Settings>,
+	observer: Res<Observer>,
+	star_field: Res<StarField>,
+	mut materials: ResMut<Assets<StarMaterial>>,
+) {
+	if !settings.is_changed() && !settings.atmosphere {
+		return;
+	}
+
+	let zenith = atmosphere::zenith(&settings, &observer);
+
+	if let Some(material) = materials.get_mut(&star_field.material) {
+		material.params = StarParams::new(&settings, zenith, time.elapsed_seconds());
+	}
+}
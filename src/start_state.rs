@@ -4,20 +4,35 @@ use std::f64::consts::PI;
 use crate::GameState;
 use crate::StartMenu;
 use crate::Player;
+use crate::SkyCultures;
+use crate::Locale;
+use crate::Localized;
+use crate::ActionInput;
+use crate::InputMap;
+use crate::GameRng;
+use crate::Tours;
+use crate::Settings;
+
+use crate::locale::localized;
+
+use crate::MenuItem;
+use crate::menu::KeyboardOnly;
+
+use crate::input::Action;
+use crate::seed::DAILY_CULTURE;
 
 #[derive(Component)]
-struct AudioPlayer;
-
-pub fn audio_setup(asset_server: Res<AssetServer>, mut commands: Commands) {
-    commands.spawn((AudioBundle {
-        source: asset_server.load("Banjo.ogg"),
-        settings: PlaybackSettings::LOOP,
-    }, AudioPlayer));
-    info!("audio started");
-}
+pub struct CultureLabel;
+
+#[derive(Component)]
+pub struct LanguageLabel;
+
+#[derive(Component)]
+pub struct TourLabel;
 
 pub fn setup(
 	mut commands: Commands,
+	input_map: Res<InputMap>,
 ) {
     let main_container_node = NodeBundle {
         style: Style {
@@ -55,41 +70,188 @@ pub fn setup(
         ..default()
     };
 
+    let culture_text_style = TextStyle {
+        font_size: 20.0, 
+        color: Color::srgb(0.4,0.4,0.4),
+        // font: asset_server.load("fonts/FiraSans-Regular.ttf"), // Load font if needed
+        ..default()
+    };
+
     let title_text_node = TextBundle::from_section(
         "Astraea", 
         title_text_style,
     );
 
     let start_text_node = TextBundle::from_section(
-        "Press Space to Begin", 
+        "", 
         start_text_style,
     );
 
     let explo_text_node = TextBundle::from_section(
-        "Press E to Explore", 
+        "", 
+        explo_text_style.clone(),
+    );
+
+    let daily_text_node = TextBundle::from_section(
+        "", 
+        explo_text_style.clone(),
+    );
+
+    let seed_text_node = TextBundle::from_section(
+        "", 
+        explo_text_style.clone(),
+    );
+
+    let tour_text_node = TextBundle::from_section(
+        "", 
         explo_text_style,
     );
 
+    let bindings_text_node = TextBundle::from_section(
+        "", 
+        culture_text_style.clone(),
+    );
+
+    let culture_text_node = TextBundle::from_section(
+        "", 
+        culture_text_style.clone(),
+    ).with_style(Style {
+        margin: UiRect::top(Val::Px(20.0)),
+        ..default()
+    });
+
+    let language_text_node = TextBundle::from_section(
+        "", 
+        culture_text_style.clone(),
+    );
+
+    let settings_text_node = TextBundle::from_section(
+        "", 
+        culture_text_style,
+    );
+
     let title_text = commands.spawn((title_text_node, StartMenu)).id();
-    let start_text = commands.spawn((start_text_node, StartMenu)).id();
-    let explo_text = commands.spawn((explo_text_node, StartMenu)).id();
+    let start_text = commands.spawn((
+    	start_text_node,
+    	StartMenu,
+    	MenuItem { action: Action::Next, order: 0 },
+    	Interaction::default(),
+    	Localized::new("start-begin").with_arg("key", input_map.key_name(Action::Next)),
+    )).id();
+    let daily_text = commands.spawn((
+    	daily_text_node,
+    	StartMenu,
+    	MenuItem { action: Action::DailyChallenge, order: 1 },
+    	Interaction::default(),
+    	Localized::new("start-daily").with_arg("key", input_map.key_name(Action::DailyChallenge)),
+    )).id();
+    let seed_text = commands.spawn((
+    	seed_text_node,
+    	StartMenu,
+    	KeyboardOnly,
+    	MenuItem { action: Action::EnterSeed, order: 2 },
+    	Interaction::default(),
+    	Localized::new("start-seed").with_arg("key", input_map.key_name(Action::EnterSeed)),
+    )).id();
+    let explo_text = commands.spawn((
+    	explo_text_node,
+    	StartMenu,
+    	MenuItem { action: Action::Explore, order: 3 },
+    	Interaction::default(),
+    	Localized::new("start-explore").with_arg("key", input_map.key_name(Action::Explore)),
+    )).id();
+    let tour_text = commands.spawn((
+    	tour_text_node,
+    	StartMenu,
+    	TourLabel,
+    	MenuItem { action: Action::Tour, order: 4 },
+    	Interaction::default(),
+    )).id();
+    let bindings_text = commands.spawn((
+    	bindings_text_node,
+    	StartMenu,
+    	KeyboardOnly,
+    	MenuItem { action: Action::KeyBindings, order: 7 },
+    	Interaction::default(),
+    	Localized::new("start-bindings").with_arg("key", input_map.key_name(Action::KeyBindings)),
+    )).id();
+    let culture_text = commands.spawn((
+    	culture_text_node,
+    	StartMenu,
+    	CultureLabel,
+    	MenuItem { action: Action::ChangeCulture, order: 5 },
+    	Interaction::default(),
+    )).id();
+    let language_text = commands.spawn((
+    	language_text_node,
+    	StartMenu,
+    	LanguageLabel,
+    	MenuItem { action: Action::ChangeLanguage, order: 6 },
+    	Interaction::default(),
+    )).id();
+
+    let settings_text = commands.spawn((
+    	settings_text_node,
+    	StartMenu,
+    	MenuItem { action: Action::Settings, order: 8 },
+    	Interaction::default(),
+    	Localized::new("start-settings").with_arg("key", input_map.key_name(Action::Settings)),
+    )).id();
 
-    commands.entity(main_container).push_children(&[title_text, start_text, explo_text]);
+    commands.entity(main_container).push_children(&[title_text, start_text, daily_text, seed_text, explo_text, tour_text, culture_text, language_text, bindings_text, settings_text]);
 }
 
 pub fn player_interact(
-	keys: Res<ButtonInput<KeyCode>>,
+	actions: ActionInput,
 	mut game_state: ResMut<NextState<GameState>>,
 	mut player_query: Query<(&mut Player, &mut Transform)>,
+	mut cultures: ResMut<SkyCultures>,
+	mut game_rng: ResMut<GameRng>,
+	mut tours: ResMut<Tours>,
+	settings: Res<Settings>,
 ) {
-	if keys.just_pressed(KeyCode::Space) {
-		game_state.set(GameState::Game);
+	// First games go through the tutorial
+	let game = if settings.tutorial { GameState::Tutorial } else { GameState::Game };
+
+	if actions.just_pressed(Action::Next) {
+		*game_rng = GameRng::random();
+		game_state.set(game);
+	}
+
+	if actions.just_pressed(Action::DailyChallenge) {
+		cultures.play_on(DAILY_CULTURE);
+		*game_rng = GameRng::daily();
+		game_state.set(game);
+	}
+
+	if actions.just_pressed(Action::EnterSeed) {
+		game_state.set(GameState::SeedEntry);
 	}
 
-	if keys.just_pressed(KeyCode::KeyE) {
+	if actions.just_pressed(Action::Explore) {
 		game_state.set(GameState::Explo);
 	}
 
+	if actions.just_pressed(Action::Tour) {
+		game_state.set(GameState::Tour);
+	}
+
+	if actions.just_pressed(Action::ChangeTour) {
+		tours.select_next();
+	}
+
+	if actions.just_pressed(Action::KeyBindings) {
+		game_state.set(GameState::Bindings);
+	}
+
+	if actions.just_pressed(Action::Settings) {
+		game_state.set(GameState::Settings);
+	}
+
+	if actions.just_pressed(Action::ChangeCulture) {
+		cultures.select_next();
+	}
+
 	if let Ok((_player, mut transform)) = player_query.get_single_mut() {
 		let mut rotation = Quat::IDENTITY;
 		rotation *= Quat::from_rotation_y((PI / 6000.0) as f32);
@@ -97,3 +259,38 @@ pub fn player_interact(
 	    transform.rotation *= rotation; 
 	}
 }
+
+pub fn menu_labels(
+	cultures: Res<SkyCultures>,
+	locale: Res<Locale>,
+	input_map: Res<InputMap>,
+	mut culture_label_query: Query<&mut Text, (With<CultureLabel>, Without<LanguageLabel>, Without<TourLabel>)>,
+	mut language_label_query: Query<&mut Text, (With<LanguageLabel>, Without<CultureLabel>, Without<TourLabel>)>,
+	mut tour_label_query: Query<&mut Text, (With<TourLabel>, Without<CultureLabel>, Without<LanguageLabel>)>,
+	tours: Res<Tours>,
+) {
+	if let Ok(mut label) = culture_label_query.get_single_mut() {
+		let culture = cultures.current();
+		let culture_line = locale.text_with("culture-label", &[
+			("name".into(), locale.culture(&culture.name)),
+			("key".into(), input_map.key_name(Action::ChangeCulture)),
+		]);
+
+		label.sections[0].value = format!("{}\n{}", culture_line, locale.culture_description(&culture.name, &culture.description));
+	}
+
+	if let Ok(mut label) = language_label_query.get_single_mut() {
+		label.sections[0].value = locale.text_with("language-label", &[
+			("language".into(), locale.text("language-name")),
+			("key".into(), input_map.key_name(Action::ChangeLanguage)),
+		]);
+	}
+
+	if let Ok(mut label) = tour_label_query.get_single_mut() {
+		label.sections[0].value = locale.text_with("start-tour", &[
+			("key".into(), input_map.key_name(Action::Tour)),
+			("name".into(), localized(&tours.current().name, &locale).to_string()),
+			("change".into(), input_map.key_name(Action::ChangeTour)),
+		]);
+	}
+}
@@ -0,0 +1,278 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::Player;
+use crate::GameState;
+use crate::MainGame;
+use crate::ConstellationModel;
+use crate::Constellation;
+use crate::SkyCultures;
+use crate::Locale;
+use crate::Localized;
+use crate::ActionInput;
+use crate::InputMap;
+use crate::Settings;
+
+use crate::input::Action;
+use crate::locale::Texts;
+use crate::locale::localized;
+use crate::bookmarks::view_rotation;
+use crate::chart::celestial_coordinates;
+
+use crate::spawn_cons_lines;
+use crate::celestial_to_cartesian;
+
+use crate::MIN_FOV;
+use crate::MAX_FOV;
+
+fn default_fov() -> f32 {
+	60.0
+}
+
+fn default_duration() -> f32 {
+	8.0
+}
+
+// Where the camera goes, given by a western constellation or coordinates,
+// the figures drawn and what is said meanwhile
+#[derive(Deserialize, Clone, Debug)]
+pub struct TourStep {
+	#[serde(default)]
+	pub constellation: Option<String>,
+	#[serde(default)]
+	pub ra: Option<f64>,
+	#[serde(default)]
+	pub dec: Option<f64>,
+	#[serde(default = "default_fov")]
+	pub fov: f32,
+	#[serde(default)]
+	pub highlight: Vec<String>,
+	pub caption: Texts,
+	// Seconds before the next step, the camera moves included
+	#[serde(default = "default_duration")]
+	pub duration: f32,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct Tour {
+	pub name: Texts,
+	pub steps: Vec<TourStep>,
+}
+
+#[derive(Resource)]
+pub struct Tours {
+	pub content: Vec<Tour>,
+	pub selected: usize,
+	step: usize,
+	step_time: f32,
+	shown: Option<usize>,
+}
+
+impl Default for Tours {
+	fn default() -> Self {
+		Tours {
+			content: load_tours(),
+			selected: 0,
+			step: 0,
+			step_time: 0.0,
+			shown: None,
+		}
+	}
+}
+
+impl Tours {
+	pub fn current(&self) -> &Tour {
+		&self.content[self.selected]
+	}
+
+	pub fn select_next(&mut self) {
+		self.selected = (self.selected + 1) % self.content.len();
+	}
+
+	// Step being played, None once the tour is over
+	pub fn step(&self) -> Option<usize> {
+		(self.step < self.current().steps.len()).then_some(self.step)
+	}
+}
+
+#[derive(Component)]
+pub struct TourCaption;
+
+pub fn load_tours() -> Vec<Tour> {
+	let tour_files = [
+		include_str!("../data/tours/winter_hexagon.json"),
+		include_str!("../data/tours/big_dipper_to_polaris.json"),
+	];
+
+	tour_files.iter()
+		.map(|json| serde_json::from_str(json).expect("invalid tour json, run astraea-data"))
+		.collect()
+}
+
+// Steps naming constellations missing from the western sky, or without a target
+pub fn validate_tours(constellations_json: &str) -> Vec<String> {
+	let constellations : Vec<Constellation> = match serde_json::from_str(constellations_json) {
+		Ok(constellations) => constellations,
+		Err(error) => return vec![format!("constellations : {}", error)],
+	};
+	let known = |name: &str| constellations.iter().any(|constellation| constellation.name == name);
+
+	let mut errors = vec![];
+	for tour in load_tours() {
+		let tour_name = tour.name.get("en").cloned().unwrap_or_default();
+		if tour.steps.is_empty() {
+			errors.push(format!("tour {} : no steps", tour_name));
+		}
+
+		for (index, step) in tour.steps.iter().enumerate() {
+			let names = step.constellation.iter().chain(step.highlight.iter());
+			for name in names.filter(|name| !known(name)) {
+				errors.push(format!("tour {}, step {} : unknown constellation {}", tour_name, index + 1, name));
+			}
+			if step.constellation.is_none() && (step.ra.is_none() || step.dec.is_none()) {
+				errors.push(format!("tour {}, step {} : no constellation nor coordinates", tour_name, index + 1));
+			}
+			if !step.caption.contains_key("en") {
+				errors.push(format!("tour {}, step {} : no english caption", tour_name, index + 1));
+			}
+		}
+	}
+	errors
+}
+
+// Right ascension and declination the step looks at
+fn step_target(step: &TourStep, figures: &[Constellation]) -> Option<(f64, f64)> {
+	if let (Some(ra), Some(dec)) = (step.ra, step.dec) {
+		return Some((ra, dec));
+	}
+
+	let constellation = figures.iter().find(|constellation| Some(&constellation.name) == step.constellation.as_ref())?;
+	let center = constellation.stars.iter().map(|star| celestial_to_cartesian(star.rah, star.dec)).sum::<Vec3>().try_normalize()?;
+	let (rah, dec) = celestial_coordinates(center);
+	Some((rah as f64, dec as f64))
+}
+
+pub fn setup(
+	mut commands: Commands,
+	mut tours: ResMut<Tours>,
+	input_map: Res<InputMap>,
+) {
+	tours.step = 0;
+	tours.step_time = 0.0;
+	tours.shown = None;
+
+	let container = commands.spawn((
+		NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				width: Val::Percent(100.0),
+				bottom: Val::Px(30.0),
+				flex_direction: FlexDirection::Column,
+				align_items: AlignItems::Center,
+				..default()
+			},
+			..default()
+		},
+		MainGame,
+	)).id();
+
+	let caption = commands.spawn((
+		TextBundle::from_section("", TextStyle {
+			font_size: 24.0,
+			color: Color::WHITE,
+			..default()
+		}).with_text_justify(JustifyText::Center).with_style(Style {
+			max_width: Val::Px(800.0),
+			..default()
+		}),
+		TourCaption,
+	)).id();
+
+	let help = commands.spawn((
+		TextBundle::from_section("", TextStyle {
+			font_size: 18.0,
+			color: Color::srgb(0.4, 0.4, 0.4),
+			..default()
+		}).with_style(Style {
+			margin: UiRect::top(Val::Px(10.0)),
+			..default()
+		}),
+		Localized::new("tour-help")
+			.with_arg("next", input_map.key_name(Action::Next))
+			.with_arg("back", input_map.key_name(Action::Back)),
+	)).id();
+
+	commands.entity(container).push_children(&[caption, help]);
+}
+
+// Steps move on by themselves after their duration, or with Next
+pub fn play(
+	time: Res<Time>,
+	actions: ActionInput,
+	locale: Res<Locale>,
+	settings: Res<Settings>,
+	cultures: Res<SkyCultures>,
+	mut tours: ResMut<Tours>,
+	mut game_state: ResMut<NextState<GameState>>,
+	mut player_query: Query<&mut Player>,
+	mut caption_query: Query<&mut Text, With<TourCaption>>,
+	constellation_query: Query<(Entity, &ConstellationModel)>,
+	mut commands: Commands,
+	mut meshes: ResMut<Assets<Mesh>>,
+	mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+	if actions.just_pressed(Action::Back) {
+		game_state.set(GameState::Start);
+		return;
+	}
+
+	tours.step_time += time.delta_seconds();
+
+	if let Some(step) = tours.step() {
+		if actions.just_pressed(Action::Next) || tours.step_time > tours.current().steps[step].duration {
+			tours.step += 1;
+			tours.step_time = 0.0;
+		}
+	}
+
+	let Some(step_index) = tours.step() else {
+		info!("tour over");
+		game_state.set(GameState::Start);
+		return;
+	};
+
+	if let Ok(mut caption) = caption_query.get_single_mut() {
+		if tours.shown != Some(step_index) || locale.is_changed() {
+			caption.sections[0].value = localized(&tours.current().steps[step_index].caption, &locale).to_string();
+		}
+	}
+
+	if tours.shown == Some(step_index) {
+		return;
+	}
+	tours.shown = Some(step_index);
+
+	// Tours are written for the western figures, whatever the culture picked
+	let figures = &cultures.content[0].constellations;
+	let step = &tours.current().steps[step_index];
+
+	if let (Ok(mut player), Some((rah, dec))) = (player_query.get_single_mut(), step_target(step, figures)) {
+		player.target_rotation = Some(view_rotation(rah, dec, 0.0));
+		player.target_fov = Some(step.fov.to_radians().clamp(MIN_FOV, MAX_FOV));
+	}
+
+	for (entity, constellation) in constellation_query.iter() {
+		if !step.highlight.contains(&constellation.name) {
+			commands.entity(entity).despawn_recursive();
+		}
+	}
+
+	for name in step.highlight.iter() {
+		if constellation_query.iter().any(|(_, constellation)| &constellation.name == name) {
+			continue;
+		}
+		if let Some(constellation) = figures.iter().find(|constellation| &constellation.name == name) {
+			spawn_cons_lines(&mut commands, &mut meshes, &mut materials, constellation.clone(), settings.line_brightness);
+		}
+	}
+}
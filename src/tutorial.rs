@@ -0,0 +1,253 @@
+use bevy::prelude::*;
+
+use crate::Player;
+use crate::GameState;
+use crate::GameData;
+use crate::MainGame;
+use crate::Locale;
+use crate::Localized;
+use crate::ActionInput;
+use crate::InputMap;
+use crate::Settings;
+
+use crate::input::Action;
+
+// Seconds a step is shown before it can be done, so that one long drag does not go through several
+const STEP_DELAY: f32 = 1.0;
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum TutorialStep {
+	Pan,
+	Rotate,
+	Zoom,
+	Recenter,
+	Hint,
+	Answer,
+}
+
+impl TutorialStep {
+	const ALL: [TutorialStep; 6] = [
+		TutorialStep::Pan,
+		TutorialStep::Rotate,
+		TutorialStep::Zoom,
+		TutorialStep::Recenter,
+		TutorialStep::Hint,
+		TutorialStep::Answer,
+	];
+
+	fn locale_key(&self) -> String {
+		format!("tutorial-{:?}", self).to_lowercase()
+	}
+
+	fn next(&self) -> Option<TutorialStep> {
+		let index = TutorialStep::ALL.iter().position(|step| step == self)?;
+		TutorialStep::ALL.get(index + 1).copied()
+	}
+
+	// Moving around is learnt in the sky, the rest on the first question
+	fn in_quiz(&self) -> bool {
+		!matches!(self, TutorialStep::Pan | TutorialStep::Rotate | TutorialStep::Zoom)
+	}
+}
+
+#[derive(Resource, Default)]
+pub struct Tutorial {
+	step: Option<TutorialStep>,
+	step_time: f32,
+	shown: Option<TutorialStep>,
+	// The recenter step waits for the camera to leave the constellation asked first
+	moved_away: bool,
+}
+
+impl Tutorial {
+	// Step being taught, None when there is no tutorial running
+	pub fn step(&self) -> Option<TutorialStep> {
+		self.step
+	}
+
+	fn go_to(&mut self, step: Option<TutorialStep>) {
+		self.step = step;
+		self.step_time = 0.0;
+	}
+}
+
+#[derive(Component)]
+pub struct TutorialCaption;
+
+// Once done or skipped, it is only shown again when asked for in the settings
+fn finish(tutorial: &mut Tutorial, settings: &mut Settings) {
+	info!("tutorial over");
+	tutorial.go_to(None);
+	settings.tutorial = false;
+	settings.save();
+}
+
+// In the quiz the back key leaves the game rather than the tutorial, so it is not told
+fn spawn_caption(commands: &mut Commands, input_map: &InputMap, style: Style, help: bool) {
+	let container = commands.spawn((
+		NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				width: Val::Percent(100.0),
+				flex_direction: FlexDirection::Column,
+				align_items: AlignItems::Center,
+				..style
+			},
+			..default()
+		},
+		MainGame,
+	)).id();
+
+	let caption = commands.spawn((
+		TextBundle::from_section("", TextStyle {
+			font_size: 24.0,
+			color: Color::WHITE,
+			..default()
+		}).with_text_justify(JustifyText::Center).with_style(Style {
+			max_width: Val::Px(800.0),
+			..default()
+		}),
+		TutorialCaption,
+	)).id();
+	commands.entity(container).add_child(caption);
+
+	if !help {
+		return;
+	}
+
+	let help = commands.spawn((
+		TextBundle::from_section("", TextStyle {
+			font_size: 18.0,
+			color: Color::srgb(0.4, 0.4, 0.4),
+			..default()
+		}).with_style(Style {
+			margin: UiRect::top(Val::Px(10.0)),
+			..default()
+		}),
+		Localized::new("tutorial-help").with_arg("key", input_map.key_name(Action::Back)),
+	)).id();
+
+	commands.entity(container).add_child(help);
+}
+
+pub fn setup(
+	mut commands: Commands,
+	mut tutorial: ResMut<Tutorial>,
+	input_map: Res<InputMap>,
+) {
+	tutorial.go_to(Some(TutorialStep::Pan));
+	tutorial.shown = None;
+	tutorial.moved_away = false;
+
+	spawn_caption(&mut commands, &input_map, Style {
+		bottom: Val::Px(30.0),
+		..default()
+	}, true);
+}
+
+// The steps left for the first question, above the answers
+pub fn game_setup(
+	mut commands: Commands,
+	mut tutorial: ResMut<Tutorial>,
+	input_map: Res<InputMap>,
+) {
+	if tutorial.step.is_none() {
+		return;
+	}
+	tutorial.shown = None;
+
+	spawn_caption(&mut commands, &input_map, Style {
+		top: Val::Px(90.0),
+		..default()
+	}, false);
+}
+
+// Leaving the first game halfway counts as done
+pub fn game_exit(
+	mut tutorial: ResMut<Tutorial>,
+	mut settings: ResMut<Settings>,
+) {
+	if tutorial.step.is_some() {
+		finish(&mut tutorial, &mut settings);
+	}
+}
+
+// Each step moves on once the player did what it asks
+pub fn progress(
+	time: Res<Time>,
+	actions: ActionInput,
+	locale: Res<Locale>,
+	input_map: Res<InputMap>,
+	game_data: Res<GameData>,
+	state: Res<State<GameState>>,
+	mut game_state: ResMut<NextState<GameState>>,
+	mut tutorial: ResMut<Tutorial>,
+	mut settings: ResMut<Settings>,
+	player_query: Query<&Player>,
+	mut caption_query: Query<(&Parent, &mut Text), With<TutorialCaption>>,
+	mut commands: Commands,
+) {
+	let Some(step) = tutorial.step else {
+		return;
+	};
+
+	let in_game = *state.get() == GameState::Game;
+
+	if !in_game && actions.just_pressed(Action::Back) {
+		finish(&mut tutorial, &mut settings);
+		game_state.set(GameState::Game);
+		return;
+	}
+
+	if in_game && !game_data.answers().is_empty() {
+		finish(&mut tutorial, &mut settings);
+		for (container, _) in caption_query.iter() {
+			commands.entity(container.get()).despawn_recursive();
+		}
+		return;
+	}
+
+	if in_game && game_data.target().is_some() && !game_data.focused() {
+		tutorial.moved_away = true;
+	}
+
+	tutorial.step_time += time.delta_seconds();
+
+	let Ok(player) = player_query.get_single() else {
+		return;
+	};
+
+	// Touch screens and gamepads move in several ways at once, any move will do
+	let moving = player.touch_moving || player.stick_moving;
+
+	let done = tutorial.step_time > STEP_DELAY && match step {
+		TutorialStep::Pan => moving || (player.l_drag_pos.is_some() && player.target_rotation.is_some()),
+		TutorialStep::Rotate => moving || (player.r_drag_pos.is_some() && player.target_rotation.is_some()),
+		TutorialStep::Zoom => player.target_fov.is_some(),
+		TutorialStep::Recenter => tutorial.moved_away && game_data.focused(),
+		TutorialStep::Hint => game_data.hinted(),
+		TutorialStep::Answer => false,
+	};
+
+	if done {
+		let next = step.next();
+		tutorial.go_to(next);
+		if next.is_some_and(|next| next.in_quiz()) && !in_game {
+			game_state.set(GameState::Game);
+		}
+		return;
+	}
+
+	if tutorial.shown == Some(step) && !locale.is_changed() {
+		return;
+	}
+	tutorial.shown = Some(step);
+
+	let args = [
+		("recenter".to_string(), input_map.key_name(Action::Recenter)),
+		("hint".to_string(), input_map.key_name(Action::Hint)),
+	];
+	for (_, mut caption) in caption_query.iter_mut() {
+		caption.sections[0].value = locale.text_with(&step.locale_key(), &args);
+	}
+}
@@ -0,0 +1,111 @@
+// Browser glue for the wasm build, everything else goes through bevy
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(inline_js = "
+export function storage_get(key) {
+	return window.localStorage.getItem(key);
+}
+
+export function storage_set(key, value) {
+	window.localStorage.setItem(key, value);
+}
+
+export function now_ms() {
+	return Date.now();
+}
+
+let orientation = null;
+let absolute_seen = false;
+let position = null;
+
+function screen_angle() {
+	if (window.screen && window.screen.orientation) {
+		return window.screen.orientation.angle;
+	}
+	return window.orientation || 0;
+}
+
+function listen_orientation() {
+	// Chrome gives north referenced angles in a separate event, Safari
+	// adds a compass heading to the usual one
+	window.addEventListener('deviceorientationabsolute', (event) => {
+		if (event.alpha === null) {
+			return;
+		}
+		absolute_seen = true;
+		orientation = [event.alpha, event.beta, event.gamma, screen_angle()];
+	});
+
+	window.addEventListener('deviceorientation', (event) => {
+		if (event.alpha === null) {
+			return;
+		}
+		if (event.webkitCompassHeading !== undefined) {
+			orientation = [360 - event.webkitCompassHeading, event.beta, event.gamma, screen_angle()];
+		} else if (event.absolute || !absolute_seen) {
+			orientation = [event.alpha, event.beta, event.gamma, screen_angle()];
+		}
+	});
+}
+
+export function orientation_start() {
+	// Safari only gives orientation after asking, other browsers right away
+	if (typeof DeviceOrientationEvent !== 'undefined' && typeof DeviceOrientationEvent.requestPermission === 'function') {
+		DeviceOrientationEvent.requestPermission()
+			.then((state) => {
+				if (state === 'granted') {
+					listen_orientation();
+				}
+			})
+			.catch((error) => console.warn('orientation refused', error));
+	} else {
+		listen_orientation();
+	}
+
+	if (navigator.geolocation) {
+		navigator.geolocation.getCurrentPosition((result) => {
+			position = [result.coords.latitude, result.coords.longitude];
+		});
+	}
+}
+
+export function fragment_get() {
+	const fragment = window.location.hash.slice(1);
+	return fragment.length > 0 ? decodeURIComponent(fragment) : null;
+}
+
+export function fragment_set(fragment) {
+	// Replaced rather than pushed, the back button should leave the page
+	history.replaceState(null, '', '#' + fragment);
+}
+
+export function download(name, bytes) {
+	const type = name.endsWith('.svg') ? 'image/svg+xml' : name.endsWith('.png') ? 'image/png' : 'application/octet-stream';
+	const url = URL.createObjectURL(new Blob([bytes], { type }));
+	const link = document.createElement('a');
+	link.href = url;
+	link.download = name;
+	link.click();
+	// Some browsers start the download after the click returns
+	setTimeout(() => URL.revokeObjectURL(url), 1000);
+}
+
+export function orientation_latest() {
+	return orientation;
+}
+
+export function position_latest() {
+	return position;
+}
+")]
+extern "C" {
+	pub fn storage_get(key: &str) -> Option<String>;
+	pub fn storage_set(key: &str, value: &str);
+	pub fn now_ms() -> f64;
+	pub fn fragment_get() -> Option<String>;
+	pub fn fragment_set(fragment: &str);
+	pub fn download(name: &str, bytes: &[u8]);
+	pub fn orientation_start();
+	pub fn orientation_latest() -> Option<Vec<f64>>;
+	pub fn position_latest() -> Option<Vec<f64>>;
+}
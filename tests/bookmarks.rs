@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+
+use astraea::Bookmark;
+use astraea::Bookmarks;
+use astraea::Observer;
+
+#[test]
+fn link_round_trips() {
+	let bookmark = Bookmark {
+		name: String::new(),
+		rah: 5.9195,
+		dec: 7.407,
+		fov: 40.0,
+		roll: 12.5,
+		observer: Some((50.63, 3.06)),
+		time: Some(1_760_000_000.0),
+	};
+
+	let fragment = bookmark.to_fragment();
+	assert_eq!(Bookmark::from_fragment(&format!("#{}", fragment)), Some(bookmark));
+}
+
+#[test]
+fn short_link_gets_default_field() {
+	let bookmark = Bookmark::from_fragment("ra=5.5&dec=-5").expect("right ascension and declination are enough");
+
+	assert_eq!(bookmark.roll, 0.0);
+	assert!(bookmark.fov > 0.0);
+	assert_eq!(bookmark.time, None);
+	assert!(Bookmark::from_fragment("ra=5.5").is_none());
+	assert!(Bookmark::from_fragment("ra=5.5&dec=95").is_none());
+	assert!(Bookmark::from_fragment("ra=NaN&dec=-5").is_none());
+	assert_eq!(Bookmark::from_fragment("ra=5.5&dec=-5&fov=inf").map(|bookmark| bookmark.fov), Some(bookmark.fov));
+}
+
+#[test]
+fn camera_rotation_round_trips() {
+	let rotation = Quat::from_euler(EulerRot::YXZ, 1.2, 0.4, -0.3);
+	let bookmark = Bookmark::from_camera("test", rotation, 0.7);
+	let restored = Bookmark::from_camera("test", bookmark.rotation(), 0.7);
+
+	assert!(bookmark.rotation().angle_between(rotation) < 1e-3);
+	assert!((restored.rah - bookmark.rah).abs() < 1e-3);
+	assert!((restored.roll - bookmark.roll).abs() < 1e-2);
+}
+
+#[test]
+fn own_place_comes_back_after_a_bookmark() {
+	let mut bookmarks = Bookmarks::default();
+	let mut observer = Observer::default();
+	let home = (observer.latitude, observer.longitude);
+
+	let sydney = Bookmark::from_fragment("ra=12.5&dec=-60&lat=-33.87&lon=151.21&t=1760000000").unwrap();
+	let quito = Bookmark::from_fragment("ra=6&dec=0&lat=-0.18&lon=-78.47").unwrap();
+	bookmarks.move_observer(&sydney, &mut observer);
+	bookmarks.move_observer(&quito, &mut observer);
+	assert_eq!((observer.latitude, observer.longitude), (-0.18, -78.47));
+
+	bookmarks.move_observer(&Bookmark::from_fragment("ra=6&dec=0").unwrap(), &mut observer);
+	assert_eq!((observer.latitude, observer.longitude), home);
+	assert_eq!(observer.time, None);
+}
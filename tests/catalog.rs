@@ -0,0 +1,114 @@
+use astraea::validate_catalog;
+use astraea::parse_dec;
+use astraea::parse_json_catalog;
+use astraea::encode_catalog;
+use astraea::decode_catalog;
+use astraea::convert_catalog;
+use astraea::star_names;
+use astraea::CatalogStar;
+use astraea::validate_tours;
+
+const STARS: &str = include_str!("../data/stars.json");
+const CONSTELLATIONS: &str = include_str!("../data/constellations.json");
+
+#[test]
+fn shipped_catalog_is_valid() {
+	let report = validate_catalog(STARS, CONSTELLATIONS);
+	assert!(report.is_valid(), "{}", report);
+	assert_eq!(report.constellation_count, 88);
+}
+
+#[test]
+fn shipped_tours_are_valid() {
+	let errors = validate_tours(CONSTELLATIONS);
+	assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn bad_line_index_is_reported() {
+	let mut constellations : serde_json::Value = serde_json::from_str(CONSTELLATIONS).unwrap();
+	constellations[0]["lines"][0][1] = 99.into();
+	let constellations = constellations.to_string();
+
+	let report = validate_catalog(STARS, &constellations);
+	assert!(!report.is_valid());
+	assert!(report.errors.iter().any(|error| error.contains("uses star 99")), "{}", report);
+}
+
+#[test]
+fn declinations_just_south_of_the_equator_stay_south() {
+	// HR 2, whose "-00" degrees carry the sign for the minutes and seconds
+	let dec = parse_dec("-00° 30′ 11″").expect("valid declination");
+	assert!((dec + 0.503).abs() < 0.001);
+
+	assert!((parse_dec("+00° 30′ 11″").unwrap() - 0.503).abs() < 0.001);
+	assert!((parse_dec("-08° 12′ 06″").unwrap() + 8.202).abs() < 0.001);
+}
+
+#[test]
+fn binary_catalog_round_trips() {
+	let stars = parse_json_catalog(STARS).unwrap();
+	let decoded = decode_catalog(&encode_catalog(&stars)).unwrap();
+
+	assert_eq!(decoded.len(), stars.len());
+
+	// Brightest first, with Sirius leading
+	assert_eq!(decoded[0].id, 2491);
+	assert!(decoded.windows(2).all(|pair| pair[0].magnitude <= pair[1].magnitude));
+
+	let polaris = stars.iter().find(|star| star.id == 424).unwrap();
+	let decoded_polaris = decoded.iter().find(|star| star.id == 424).unwrap();
+	assert_eq!(decoded_polaris.rah, polaris.rah);
+	assert_eq!(decoded_polaris.dec, polaris.dec);
+	assert_eq!(decoded_polaris.in_constellation, polaris.in_constellation);
+}
+
+#[test]
+fn truncated_binary_catalog_is_rejected() {
+	let stars = parse_json_catalog(STARS).unwrap();
+	let bytes = encode_catalog(&stars);
+
+	assert!(decode_catalog(&bytes[..bytes.len() - 1]).is_err());
+	assert!(decode_catalog(b"not a catalog").is_err());
+
+	// A count whose size overflows a 32 bit usize
+	let mut header = bytes[..12].to_vec();
+	header[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+	assert!(decode_catalog(&header).is_err());
+}
+
+#[test]
+fn gaia_source_ids_are_kept_exactly() {
+	let directory = std::env::temp_dir();
+	let csv = directory.join(format!("astraea-gaia-{}.csv", std::process::id()));
+	let bin = directory.join(format!("astraea-gaia-{}.bin", std::process::id()));
+
+	// Barnard's star and Proxima Centauri, from the Gaia DR3 archive
+	std::fs::write(&csv, "source_id,ra,dec,phot_g_mean_mag\n\
+		4472832130942575872,269.4486,4.7398,8.1950\n\
+		5853498713190525696,217.3929,-62.6763,8.9841\n").unwrap();
+
+	let result = convert_catalog(csv.to_str().unwrap(), bin.to_str().unwrap(), None);
+	let decoded = std::fs::read(&bin).map(|bytes| decode_catalog(&bytes));
+	let _ = std::fs::remove_file(&csv);
+	let _ = std::fs::remove_file(&bin);
+
+	assert_eq!(result, Ok((2, 0)));
+	let stars = decoded.unwrap().unwrap();
+	assert_eq!(stars[0].id, 4472832130942575872);
+	assert_eq!(stars[1].id, 5853498713190525696);
+}
+
+#[test]
+fn star_names_are_found_by_position_in_other_catalogs() {
+	// Sirius and Vega as a Hipparcos export gives them, and a faint star between them
+	let star = |id, rah, dec, magnitude| CatalogStar { id, rah, dec, magnitude, color_index: f32::NAN, in_constellation: false };
+	let stars = [
+		star(32349, 6.75248, -16.7161, -1.44),
+		star(1, 12.0, 10.0, 6.0),
+		star(91262, 18.6156, 38.7837, 0.03),
+	];
+
+	let names = star_names(&stars);
+	assert_eq!(names, vec![Some("Sirius".to_string()), None, Some("Vega".to_string())]);
+}
@@ -0,0 +1,25 @@
+use astraea::constellation_chart;
+use astraea::ChartProjection;
+
+#[test]
+fn constellation_chart_draws_figure_and_names() {
+	let svg = constellation_chart("orion", None).expect("Orion is a western constellation");
+
+	assert!(svg.starts_with("<svg"));
+	assert!(svg.trim_end().ends_with("</svg>"));
+	assert!(svg.contains(">Orion</text>"));
+	assert!(svg.contains(">Betelgeuse</text>"));
+	assert!(svg.contains("<line "));
+	assert!(svg.contains("gnomonic"));
+}
+
+#[test]
+fn chart_projection_can_be_forced() {
+	let svg = constellation_chart("Ursa Major", Some(ChartProjection::Stereographic)).unwrap();
+	assert!(svg.contains("stereographic"));
+}
+
+#[test]
+fn unknown_constellation_has_no_chart() {
+	assert!(constellation_chart("Atlantis", None).is_none());
+}
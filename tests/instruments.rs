@@ -0,0 +1,60 @@
+use astraea::Instrument;
+
+#[test]
+fn telescope_field_from_the_eyepiece() {
+	let instrument : Instrument = serde_json::from_str(r#"{
+		"type": "telescope",
+		"name": "200/1200 dobsonian, 25 mm eyepiece",
+		"focal_length": 1200,
+		"eyepiece_focal_length": 25,
+		"apparent_field": 52
+	}"#).expect("invalid telescope");
+
+	assert_eq!(instrument.magnification(), 48.0);
+	assert!((instrument.true_field() - 1.083).abs() < 0.001);
+}
+
+#[test]
+fn binoculars_give_their_field() {
+	let instrument : Instrument = serde_json::from_str(r#"{
+		"type": "binoculars",
+		"name": "10x50",
+		"magnification": 10,
+		"true_field": 6.5
+	}"#).expect("invalid binoculars");
+
+	assert_eq!(instrument.name(), "10x50");
+	assert_eq!(instrument.magnification(), 10.0);
+	assert_eq!(instrument.true_field(), 6.5);
+}
+
+#[test]
+fn instruments_without_a_field_are_invalid() {
+	let telescope = |eyepiece_focal_length: f32| Instrument::Telescope {
+		name: "200/1200".into(),
+		focal_length: 1200.0,
+		eyepiece_focal_length,
+		apparent_field: 52.0,
+	};
+
+	assert!(telescope(25.0).is_valid());
+	assert!(!telescope(0.0).is_valid());
+	assert!(!telescope(-10.0).is_valid());
+	assert!(!telescope(f32::NAN).is_valid());
+
+	let binoculars = Instrument::Binoculars {
+		name: "10x50".into(),
+		magnification: 10.0,
+		true_field: 0.0,
+	};
+	assert!(!binoculars.is_valid());
+
+	// A 100° finder at 0.5x would see more than half the sky
+	let finder = Instrument::Telescope {
+		name: "finder".into(),
+		focal_length: 100.0,
+		eyepiece_focal_length: 200.0,
+		apparent_field: 100.0,
+	};
+	assert!(!finder.is_valid());
+}
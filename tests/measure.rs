@@ -0,0 +1,23 @@
+use astraea::HandWidths;
+use astraea::angular_separation;
+use astraea::hand_widths;
+
+#[test]
+fn known_separations() {
+	// Betelgeuse to Rigel, and the pointers of the Big Dipper
+	assert!((angular_separation(5.9195, 7.407, 5.2423, -8.2016) - 18.6).abs() < 0.05);
+	assert!((angular_separation(11.0621, 61.751, 11.0307, 56.3824) - 5.37).abs() < 0.05);
+
+	// Across the 0h line and from pole to pole
+	assert!((angular_separation(23.5, 0.0, 0.5, 0.0) - 15.0).abs() < 0.01);
+	assert!((angular_separation(3.0, 90.0, 15.0, -90.0) - 180.0).abs() < 0.01);
+}
+
+#[test]
+fn angles_in_hand_widths() {
+	assert_eq!(hand_widths(0.3), HandWidths::Fingers(0));
+	assert_eq!(hand_widths(5.37), HandWidths::Fingers(5));
+	assert_eq!(hand_widths(10.2), HandWidths::Fists(1.0));
+	assert_eq!(hand_widths(18.6), HandWidths::Fists(2.0));
+	assert_eq!(hand_widths(25.0), HandWidths::Fists(2.5));
+}
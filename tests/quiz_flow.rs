@@ -0,0 +1,482 @@
+use bevy::prelude::*;
+use bevy::input::keyboard::Key;
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::ButtonState;
+use bevy::input::InputPlugin;
+use bevy::state::app::StatesPlugin;
+
+use astraea::Action;
+use astraea::AnswerButton;
+use astraea::GameData;
+use astraea::GamePlugin;
+use astraea::GameRng;
+use astraea::GameState;
+use astraea::InputMap;
+use astraea::ResultCard;
+use astraea::RunMode;
+use astraea::Settings;
+use astraea::SkyCultures;
+use astraea::Tours;
+use astraea::Tutorial;
+
+// Headless app on the start screen, with default settings and bindings
+// rather than the ones saved on this machine, and never saved over them
+fn headless_app() -> App {
+	let mut app = App::new();
+
+	app.add_plugins((MinimalPlugins, InputPlugin, StatesPlugin))
+		.insert_resource(Settings::default())
+		.insert_resource(InputMap::default())
+		.add_plugins(GamePlugin);
+
+	app.update();
+	app
+}
+
+fn start_game(app: &mut App, seed: u32) {
+	app.insert_resource(GameRng::new(seed, RunMode::Shared));
+	app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Game);
+
+	// One update to enter the state, one for the first question
+	app.update();
+	app.update();
+}
+
+fn state(app: &App) -> GameState {
+	*app.world().resource::<State<GameState>>().get()
+}
+
+fn game_data(app: &App) -> &GameData {
+	app.world().resource::<GameData>()
+}
+
+fn target(app: &App) -> String {
+	game_data(app).target().expect("no question asked").to_string()
+}
+
+fn press_action(app: &mut App, action: Action) {
+	let key_code = app.world().resource::<InputMap>().key(action);
+	press_key(app, key_code);
+}
+
+// A key that types no character
+fn press_key(app: &mut App, key_code: KeyCode) {
+	for state in [ButtonState::Pressed, ButtonState::Released] {
+		app.world_mut().send_event(KeyboardInput {
+			key_code,
+			logical_key: Key::Unidentified(bevy::input::keyboard::NativeKey::Unidentified),
+			state,
+			window: Entity::PLACEHOLDER,
+		});
+		app.update();
+	}
+}
+
+// A key typed as text, like in a seed code
+fn type_key(app: &mut App, key_code: KeyCode, character: &str) {
+	for state in [ButtonState::Pressed, ButtonState::Released] {
+		app.world_mut().send_event(KeyboardInput {
+			key_code,
+			logical_key: Key::Character(character.into()),
+			state,
+			window: Entity::PLACEHOLDER,
+		});
+		app.update();
+	}
+}
+
+// Opens the seed screen, types the code and confirms it
+fn enter_seed_code(app: &mut App, code: &str) {
+	press_action(app, Action::EnterSeed);
+	for character in code.chars().filter(char::is_ascii_alphanumeric) {
+		type_key(app, KeyCode::KeyA, &character.to_string());
+	}
+
+	for state in [ButtonState::Pressed, ButtonState::Released] {
+		app.world_mut().send_event(KeyboardInput {
+			key_code: KeyCode::Enter,
+			logical_key: Key::Enter,
+			state,
+			window: Entity::PLACEHOLDER,
+		});
+		app.update();
+	}
+}
+
+// Clicks the answer button showing a constellation, or any other one
+fn answer(app: &mut App, right: bool) {
+	let target = target(app);
+
+	let mut button_query = app.world_mut().query_filtered::<(Entity, &Children), With<Button>>();
+	let buttons : Vec<(Entity, Entity)> = button_query.iter(app.world())
+		.map(|(entity, children)| (entity, children[0]))
+		.collect();
+
+	let button = buttons.iter()
+		.find(|(_, label)| {
+			let cons_name = app.world().get::<AnswerButton>(*label).expect("answer button without label").cons_name();
+			(cons_name == target) == right
+		})
+		.map(|(button, _)| *button)
+		.expect("no matching answer button");
+
+	app.world_mut().entity_mut(button).insert(Interaction::Pressed);
+	app.update();
+	app.world_mut().entity_mut(button).insert(Interaction::None);
+}
+
+#[test]
+fn game_starts_with_a_question() {
+	let mut app = headless_app();
+	assert_eq!(state(&app), GameState::Start);
+
+	start_game(&mut app, 1);
+
+	assert_eq!(state(&app), GameState::Game);
+	assert_eq!(game_data(&app).score, 0);
+	assert_eq!(game_data(&app).health(), 3);
+
+	let mut answer_query = app.world_mut().query::<&AnswerButton>();
+	let answers : Vec<String> = answer_query.iter(app.world()).map(|answer| answer.cons_name().to_string()).collect();
+
+	assert_eq!(answers.len(), 4);
+	assert!(answers.contains(&target(&app)));
+}
+
+#[test]
+fn right_answer_scores_100() {
+	let mut app = headless_app();
+	start_game(&mut app, 2);
+
+	answer(&mut app, true);
+
+	assert_eq!(game_data(&app).score, 100);
+	assert_eq!(game_data(&app).health(), 3);
+}
+
+#[test]
+fn hinted_answer_scores_20() {
+	let mut app = headless_app();
+	start_game(&mut app, 3);
+
+	press_action(&mut app, Action::Hint);
+	answer(&mut app, true);
+
+	assert_eq!(game_data(&app).score, 20);
+}
+
+#[test]
+fn wrong_answer_costs_health() {
+	let mut app = headless_app();
+	start_game(&mut app, 4);
+
+	answer(&mut app, false);
+
+	assert_eq!(game_data(&app).score, 0);
+	assert_eq!(game_data(&app).health(), 2);
+}
+
+#[test]
+fn answering_twice_counts_once() {
+	let mut app = headless_app();
+	start_game(&mut app, 5);
+
+	answer(&mut app, true);
+	answer(&mut app, false);
+
+	assert_eq!(game_data(&app).score, 100);
+	assert_eq!(game_data(&app).health(), 3);
+}
+
+#[test]
+fn next_asks_another_constellation() {
+	let mut app = headless_app();
+	start_game(&mut app, 6);
+
+	let first = target(&app);
+	answer(&mut app, true);
+	press_action(&mut app, Action::Next);
+
+	assert_ne!(target(&app), first);
+	assert_eq!(state(&app), GameState::Game);
+}
+
+#[test]
+fn three_wrong_answers_end_the_game() {
+	let mut app = headless_app();
+	start_game(&mut app, 7);
+
+	for _ in 0..3 {
+		assert_eq!(state(&app), GameState::Game);
+		answer(&mut app, false);
+		press_action(&mut app, Action::Next);
+	}
+
+	assert_eq!(game_data(&app).health(), 0);
+	assert_eq!(state(&app), GameState::End);
+
+	press_action(&mut app, Action::Next);
+	assert_eq!(state(&app), GameState::Start);
+}
+
+#[test]
+fn game_over_makes_a_result_card() {
+	let mut app = headless_app();
+	start_game(&mut app, 8);
+
+	answer(&mut app, true);
+	press_action(&mut app, Action::Next);
+	for _ in 0..3 {
+		answer(&mut app, false);
+		press_action(&mut app, Action::Next);
+	}
+
+	assert_eq!(state(&app), GameState::End);
+	assert_eq!(game_data(&app).answers().len(), 4);
+
+	let card = &app.world().resource::<ResultCard>().svg;
+	assert!(card.starts_with("<svg"));
+	// The score and the count under the title, whole elements so the seed or date cannot match
+	let texts : Vec<&str> = card.lines().filter(|line| line.starts_with("<text")).collect();
+	assert!(texts[1].ends_with(">final score : 100</text>"));
+	assert!(texts[3].ends_with(">1 of 4 constellations found</text>"));
+	// One figure found, three missed
+	assert!(card.contains("#4cd964"));
+	assert!(card.contains("#ff5a52"));
+}
+
+#[test]
+fn typing_a_seed_code_triggers_no_shortcut() {
+	let mut app = headless_app();
+	let language = app.world().resource::<Settings>().language;
+
+	press_action(&mut app, Action::EnterSeed);
+	assert_eq!(state(&app), GameState::SeedEntry);
+
+	for (key_code, character) in [(KeyCode::KeyS, "s"), (KeyCode::KeyL, "l"), (KeyCode::KeyS, "s")] {
+		type_key(&mut app, key_code, character);
+	}
+
+	assert_eq!(state(&app), GameState::SeedEntry);
+	assert_eq!(app.world().resource::<Settings>().language, language);
+	assert!(!app.world().resource::<Settings>().muted);
+}
+
+#[test]
+fn first_game_from_a_seed_code_opens_the_tutorial() {
+	let mut app = headless_app();
+
+	enter_seed_code(&mut app, &GameRng::new(42, RunMode::Shared).code(0));
+
+	assert_eq!(state(&app), GameState::Tutorial);
+	assert_eq!(app.world().resource::<GameRng>().seed, 42);
+}
+
+#[test]
+fn seed_code_culture_lasts_one_run() {
+	let mut app = headless_app();
+	app.world_mut().resource_mut::<Settings>().tutorial = false;
+	press_action(&mut app, Action::ChangeCulture);
+
+	enter_seed_code(&mut app, &GameRng::new(42, RunMode::Shared).code(2));
+	assert_eq!(state(&app), GameState::Game);
+	assert_eq!(app.world().resource::<SkyCultures>().selected, 2);
+
+	press_action(&mut app, Action::Back);
+	assert_eq!(state(&app), GameState::Start);
+	assert_eq!(app.world().resource::<SkyCultures>().selected, 1);
+}
+
+#[test]
+fn rebinding_keys_triggers_no_shortcut() {
+	let mut app = headless_app();
+	let language = app.world().resource::<Settings>().language;
+
+	press_action(&mut app, Action::KeyBindings);
+	assert_eq!(state(&app), GameState::Bindings);
+
+	press_action(&mut app, Action::ChangeLanguage);
+	assert_eq!(app.world().resource::<Settings>().language, language);
+}
+
+#[test]
+fn azerty_binding_is_named_after_its_character() {
+	let mut app = headless_app();
+
+	press_action(&mut app, Action::KeyBindings);
+	let recenter = Action::ALL.iter().position(|action| *action == Action::Recenter).unwrap();
+	for _ in 0..recenter {
+		press_key(&mut app, KeyCode::ArrowDown);
+	}
+	press_key(&mut app, KeyCode::Enter);
+
+	// The key in the W place of QWERTY, printed Z on AZERTY
+	type_key(&mut app, KeyCode::KeyW, "z");
+
+	let input_map = app.world().resource::<InputMap>();
+	assert_eq!(input_map.key(Action::Recenter), KeyCode::KeyW);
+	assert_eq!(input_map.key_name(Action::Recenter), "Z");
+}
+
+#[test]
+fn shifted_keys_keep_their_name() {
+	let mut app = headless_app();
+
+	press_action(&mut app, Action::KeyBindings);
+	let recenter = Action::ALL.iter().position(|action| *action == Action::Recenter).unwrap();
+	for _ in 0..recenter {
+		press_key(&mut app, KeyCode::ArrowDown);
+	}
+	press_key(&mut app, KeyCode::Enter);
+
+	// The 1 of AZERTY prints & and needs Shift for the digit
+	type_key(&mut app, KeyCode::Digit1, "&");
+	assert_eq!(app.world().resource::<InputMap>().key_name(Action::Recenter), "&");
+
+	app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::ShiftLeft);
+	type_key(&mut app, KeyCode::Digit1, "1");
+	assert_eq!(app.world().resource::<InputMap>().key_name(Action::Recenter), "&");
+}
+
+#[test]
+fn back_cancels_a_rebind() {
+	let mut app = headless_app();
+	let before = app.world().resource::<InputMap>().clone();
+
+	press_action(&mut app, Action::KeyBindings);
+	press_key(&mut app, KeyCode::Enter);
+	press_action(&mut app, Action::Back);
+	assert_eq!(state(&app), GameState::Bindings);
+
+	// Menu keys cannot be bound, Enter only starts waiting again
+	press_key(&mut app, KeyCode::Enter);
+	press_key(&mut app, KeyCode::Enter);
+	press_action(&mut app, Action::Back);
+
+	let input_map = app.world().resource::<InputMap>();
+	for action in Action::ALL {
+		assert_eq!(input_map.key(action), before.key(action));
+	}
+
+	press_action(&mut app, Action::Back);
+	assert_eq!(state(&app), GameState::Start);
+}
+
+#[test]
+fn daily_challenge_is_the_same_on_every_sky_culture() {
+	let daily_targets = |culture_changes: usize| {
+		let mut app = headless_app();
+		app.world_mut().resource_mut::<Settings>().tutorial = false;
+
+		for _ in 0..culture_changes {
+			press_action(&mut app, Action::ChangeCulture);
+		}
+		press_action(&mut app, Action::DailyChallenge);
+		assert_eq!(state(&app), GameState::Game);
+
+		let mut targets = vec![];
+		for _ in 0..3 {
+			targets.push(target(&app));
+			answer(&mut app, true);
+			press_action(&mut app, Action::Next);
+		}
+
+		// The player's own choice is back for the next games
+		press_action(&mut app, Action::Back);
+		assert_eq!(state(&app), GameState::Start);
+		assert_eq!(app.world().resource::<SkyCultures>().selected, culture_changes);
+
+		targets
+	};
+
+	assert_eq!(daily_targets(0), daily_targets(2));
+}
+
+#[test]
+fn same_seed_asks_the_same_questions() {
+	let questions = |seed: u32| {
+		let mut app = headless_app();
+		start_game(&mut app, seed);
+
+		let mut targets = vec![];
+		for _ in 0..5 {
+			targets.push(target(&app));
+			answer(&mut app, true);
+			press_action(&mut app, Action::Next);
+		}
+		targets
+	};
+
+	assert_eq!(questions(42), questions(42));
+	assert_ne!(questions(42), questions(43));
+}
+
+#[test]
+fn small_sky_culture_plays_to_the_end() {
+	let mut app = headless_app();
+
+	// Western, Chinese, then the five Norse figures
+	press_action(&mut app, Action::ChangeCulture);
+	press_action(&mut app, Action::ChangeCulture);
+	start_game(&mut app, 10);
+
+	let mut asked = 0;
+	for _ in 0..20 {
+		if state(&app) != GameState::Game {
+			break;
+		}
+
+		let mut answer_query = app.world_mut().query::<&AnswerButton>();
+		let mut answers : Vec<String> = answer_query.iter(app.world()).map(|answer| answer.cons_name().to_string()).collect();
+		assert!(answers.contains(&target(&app)));
+		answers.sort();
+		answers.dedup();
+		assert_eq!(answers.len(), 4);
+
+		answer(&mut app, true);
+		press_action(&mut app, Action::Next);
+		asked += 1;
+	}
+
+	assert_eq!(state(&app), GameState::End);
+	assert_eq!(asked, 5);
+	assert_eq!(game_data(&app).score, 500);
+}
+
+#[test]
+fn tour_steps_through_and_returns_to_start() {
+	let mut app = headless_app();
+
+	press_action(&mut app, Action::Tour);
+	assert_eq!(state(&app), GameState::Tour);
+	assert_eq!(app.world().resource::<Tours>().step(), Some(0));
+
+	press_action(&mut app, Action::Next);
+	assert_eq!(app.world().resource::<Tours>().step(), Some(1));
+
+	press_action(&mut app, Action::Back);
+	assert_eq!(state(&app), GameState::Start);
+}
+
+#[test]
+fn first_game_opens_the_tutorial_which_can_be_skipped() {
+	let mut app = headless_app();
+
+	press_action(&mut app, Action::Next);
+	assert_eq!(state(&app), GameState::Tutorial);
+	assert!(app.world().resource::<Tutorial>().step().is_some());
+
+	press_action(&mut app, Action::Back);
+	app.update();
+	assert_eq!(state(&app), GameState::Game);
+	assert!(app.world().resource::<Tutorial>().step().is_none());
+	assert!(!app.world().resource::<Settings>().tutorial);
+	assert!(game_data(&app).target().is_some());
+
+	// Only once
+	press_action(&mut app, Action::Back);
+	assert_eq!(state(&app), GameState::Start);
+	press_action(&mut app, Action::Next);
+	app.update();
+	assert_eq!(state(&app), GameState::Game);
+}